@@ -0,0 +1,70 @@
+//! Point updates and range sums on the lazy and the bottom-up segment trees.
+//!
+//! Run with `cargo bench --bench segment_tree`.
+use cplit::data_structure::segment_tree::AddSum;
+use cplit::data_structure::{BottomUpSegmentTree, SegmentTree};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[path = "../src/utils/xorshift.rs"]
+#[allow(dead_code)]
+mod xorshift;
+
+use xorshift::Xorshift;
+
+const N: usize = 1 << 20;
+const Q: usize = 1 << 21;
+
+/// A fixed pseudo-random sequence of `(index, value, range)` operations.
+fn operations() -> Vec<(usize, i64, usize, usize)> {
+    let mut rng = Xorshift::new(0x510e527fade682d1);
+    (0..Q)
+        .map(|_| {
+            let (a, b) = (
+                rng.next_u64() as usize % N + 1,
+                rng.next_u64() as usize % N + 1,
+            );
+            (
+                rng.next_u64() as usize % N + 1,
+                rng.next_u64() as i64 % 1000,
+                a.min(b),
+                a.max(b),
+            )
+        })
+        .collect()
+}
+
+fn time(name: &str, f: impl FnOnce() -> i64) -> Duration {
+    let start = Instant::now();
+    black_box(f());
+    let elapsed = start.elapsed();
+    println!("{:<24}{:>10.2?}", name, elapsed);
+    elapsed
+}
+
+fn main() {
+    let v: Vec<i64> = (0..=N as i64).collect();
+    let ops = operations();
+    let lazy = time("SegmentTree", || {
+        let mut st = SegmentTree::<i64, i64, AddSum>::from(v.clone());
+        let mut acc = 0;
+        for &(i, x, l, r) in &ops {
+            st.set(i, x);
+            acc ^= st.query(l..=r);
+        }
+        acc
+    });
+    let bottom_up = time("BottomUpSegmentTree", || {
+        let mut st = BottomUpSegmentTree::<i64, AddSum>::from(v.clone());
+        let mut acc = 0;
+        for &(i, x, l, r) in &ops {
+            st.set(i, x);
+            acc ^= st.query(l..=r);
+        }
+        acc
+    });
+    println!(
+        "speedup {:.2}x",
+        lazy.as_secs_f64() / bottom_up.as_secs_f64()
+    );
+}
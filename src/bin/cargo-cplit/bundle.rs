@@ -0,0 +1,737 @@
+//! Inlines the used modules of cplit into a single source file.
+
+use crate::lexer::{tokenize, Kind, Token};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+type ModPath = Vec<String>;
+
+/// A `use` tree, flattened into its leaves.
+#[derive(Debug, Clone)]
+struct UseLeaf {
+    /// Path segments as written, ending with the imported name, `self` or `*`.
+    path: Vec<String>,
+    alias: Option<String>,
+}
+
+#[derive(Debug)]
+struct UseItem {
+    public: bool,
+    /// Brace depth of the `use` keyword.
+    depth: usize,
+    /// Byte range of the whole item, including attributes and `;`.
+    item: Range<usize>,
+    /// Byte range of the tree, between `use` and `;`.
+    tree: Range<usize>,
+    leaves: Vec<UseLeaf>,
+}
+
+/// A parsed source file, either a module of cplit or the solution.
+#[derive(Debug)]
+struct Source {
+    src: String,
+    tokens: Vec<Token>,
+    uses: Vec<UseItem>,
+    /// `mod name;` declarations: name, byte range of the item, byte range of the `;`.
+    children: Vec<(String, Range<usize>, Range<usize>)>,
+    /// Names of `#[macro_export]` macros defined here.
+    macros: Vec<String>,
+}
+
+impl Source {
+    fn new(src: String) -> Self {
+        let tokens = tokenize(&src);
+        let mut source = Source {
+            src,
+            tokens,
+            uses: vec![],
+            children: vec![],
+            macros: vec![],
+        };
+        source.parse();
+        source
+    }
+
+    fn tok(&self, idx: usize) -> &str {
+        self.tokens
+            .get(idx)
+            .map_or("", |t| &self.src[t.span.clone()])
+    }
+
+    /// Returns the index of the first token of the item whose keyword is at `idx`,
+    /// including visibility and attributes.
+    fn item_start(&self, mut idx: usize) -> usize {
+        if idx >= 1 && self.tok(idx - 1) == ")" && idx >= 4 && self.tok(idx - 4) == "pub" {
+            idx -= 4;
+        } else if idx >= 1 && self.tok(idx - 1) == "pub" {
+            idx -= 1;
+        }
+        while idx >= 1 && self.tok(idx - 1) == "]" {
+            let mut j = idx - 1;
+            let mut depth = 0;
+            loop {
+                match self.tok(j) {
+                    "]" => depth += 1,
+                    "[" => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    break;
+                }
+                j -= 1;
+            }
+            if j >= 1 && self.tok(j - 1) == "#" {
+                idx = j - 1;
+            } else {
+                break;
+            }
+        }
+        idx
+    }
+
+    fn parse(&mut self) {
+        let mut depth = 0;
+        let mut i = 0;
+        while i < self.tokens.len() {
+            match self.tok(i) {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                "use" if self.tokens[i].kind == Kind::Ident => {
+                    let start = self.item_start(i);
+                    let public = self.joined(start..i);
+                    let end = (i..self.tokens.len())
+                        .find(|&j| self.tok(j) == ";")
+                        .unwrap();
+                    let leaves =
+                        parse_use_tree(&(i + 1..end).map(|j| self.tok(j)).collect::<Vec<_>>());
+                    self.uses.push(UseItem {
+                        public: public.contains("pub"),
+                        depth,
+                        item: self.tokens[start].span.start..self.tokens[end].span.end,
+                        tree: self.tokens[i + 1].span.start..self.tokens[end].span.start,
+                        leaves,
+                    });
+                    i = end;
+                }
+                "mod" if self.tok(i + 2) == ";" => {
+                    let start = self.item_start(i);
+                    self.children.push((
+                        self.tok(i + 1).to_string(),
+                        self.tokens[start].span.start..self.tokens[i + 2].span.end,
+                        self.tokens[i + 2].span.clone(),
+                    ));
+                }
+                "macro_rules" if self.tok(i + 1) == "!" => {
+                    let start = self.item_start(i);
+                    if self.joined(start..i).contains("macro_export") {
+                        self.macros.push(self.tok(i + 2).to_string());
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    /// Texts of the tokens in `range`, separated by spaces.
+    fn joined(&self, range: Range<usize>) -> String {
+        range.map(|j| self.tok(j)).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Whether the token at `idx` lies in a `use` tree.
+    fn in_use(&self, idx: usize) -> bool {
+        let pos = self.tokens[idx].span.start;
+        self.uses.iter().any(|u| u.tree.contains(&pos))
+    }
+
+    /// Paths outside `use` items starting with one of `roots`, e.g. `crate::io::Scanner::new`.
+    /// Returns the index of the first token and the segments.
+    fn inline_paths(&self, roots: &[&str]) -> Vec<(usize, Vec<String>)> {
+        let mut paths = vec![];
+        for i in 0..self.tokens.len() {
+            if roots.contains(&self.tok(i))
+                && self.tokens.get(i + 1).map(|t| t.kind) == Some(Kind::PathSep)
+                && (i == 0 || self.tokens[i - 1].kind != Kind::PathSep || self.tok(i) == "cplit")
+                && !self.in_use(i)
+            {
+                let mut path = vec![self.tok(i).to_string()];
+                let mut j = i + 1;
+                while self.tokens.get(j).map(|t| t.kind) == Some(Kind::PathSep)
+                    && self.tokens.get(j + 1).map(|t| t.kind) == Some(Kind::Ident)
+                {
+                    path.push(self.tok(j + 1).to_string());
+                    j += 2;
+                }
+                paths.push((i, path));
+            }
+        }
+        paths
+    }
+
+    /// Names invoked as macros, like `scanln!(...)`.
+    fn macro_calls(&self) -> BTreeSet<String> {
+        (1..self.tokens.len().saturating_sub(1))
+            .filter(|&i| {
+                self.tok(i) == "!"
+                    && self.tokens[i - 1].kind == Kind::Ident
+                    && matches!(self.tok(i + 1), "(" | "[" | "{")
+            })
+            .map(|i| self.tok(i - 1).to_string())
+            .collect()
+    }
+
+    /// Identifiers outside `use` items and `mod` declarations.
+    fn body_idents(&self) -> BTreeSet<&str> {
+        (0..self.tokens.len())
+            .filter(|&i| {
+                let pos = self.tokens[i].span.start;
+                self.tokens[i].kind == Kind::Ident
+                    && !self.in_use(i)
+                    && !self.children.iter().any(|c| c.1.contains(&pos))
+            })
+            .map(|i| self.tok(i))
+            .collect()
+    }
+}
+
+/// Flattens a `use` tree given as tokens.
+fn parse_use_tree(tokens: &[&str]) -> Vec<UseLeaf> {
+    fn parse(tokens: &[&str], pos: &mut usize, prefix: &mut Vec<String>, out: &mut Vec<UseLeaf>) {
+        let len = prefix.len();
+        loop {
+            match tokens.get(*pos) {
+                Some(&"::") => *pos += 1,
+                Some(&"{") => {
+                    *pos += 1;
+                    while tokens[*pos] != "}" {
+                        parse(tokens, pos, prefix, out);
+                        if tokens[*pos] == "," {
+                            *pos += 1;
+                        }
+                    }
+                    *pos += 1;
+                    break;
+                }
+                Some(&seg) if seg != "," && seg != "}" => {
+                    prefix.push(seg.to_string());
+                    *pos += 1;
+                    if tokens.get(*pos) == Some(&"as") {
+                        out.push(UseLeaf {
+                            path: prefix.clone(),
+                            alias: Some(tokens[*pos + 1].to_string()),
+                        });
+                        *pos += 2;
+                        break;
+                    }
+                    if tokens.get(*pos) != Some(&"::") {
+                        out.push(UseLeaf {
+                            path: prefix.clone(),
+                            alias: None,
+                        });
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+        prefix.truncate(len);
+    }
+
+    let mut out = vec![];
+    let mut pos = 0;
+    parse(tokens, &mut pos, &mut vec![], &mut out);
+    out
+}
+
+/// Renders `use` leaves back into a tree, skipping `;`.
+fn render_use_tree(leaves: &[UseLeaf]) -> String {
+    let render = |leaf: &UseLeaf| {
+        let mut path = leaf.path.clone();
+        if path.len() > 1 && path.last().unwrap() == "self" {
+            path.pop();
+        }
+        match &leaf.alias {
+            Some(alias) => format!("{} as {}", path.join("::"), alias),
+            None => path.join("::"),
+        }
+    };
+    match leaves {
+        [leaf] => render(leaf),
+        _ => format!(
+            "{{{}}}",
+            leaves.iter().map(render).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Removes doc comments and `#[cfg(test)]` items.
+fn strip(src: &str) -> String {
+    let source = Source::new(src.to_string());
+    let tokens = &source.tokens;
+    let mut removed: Vec<Range<usize>> = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i].kind == Kind::DocComment {
+            removed.push(tokens[i].span.clone());
+        } else if source.joined(i..i + 7) == "# [ cfg ( test ) ]" {
+            let mut j = i + 7;
+            let mut depth = 0;
+            loop {
+                match source.tok(j) {
+                    "(" | "[" => depth += 1,
+                    ")" | "]" => depth -= 1,
+                    "{" => {
+                        depth += 1;
+                        if depth == 1 {
+                            // Skip to the matching brace.
+                            let mut d = 1;
+                            while d > 0 {
+                                j += 1;
+                                match source.tok(j) {
+                                    "{" => d += 1,
+                                    "}" => d -= 1,
+                                    _ => {}
+                                }
+                            }
+                            break;
+                        }
+                    }
+                    "}" => depth -= 1,
+                    ";" if depth == 0 => break,
+                    _ => {}
+                }
+                j += 1;
+            }
+            removed.push(tokens[i].span.start..tokens[j].span.end);
+            i = j;
+        }
+        i += 1;
+    }
+    let mut out = String::new();
+    let mut last = 0;
+    for mut range in removed {
+        // Take the indentation along.
+        let line = src[..range.start].rfind('\n').map_or(0, |p| p + 1);
+        if src[line..range.start].trim().is_empty() {
+            range.start = line.max(last);
+        }
+        out.push_str(&src[last..range.start]);
+        last = range.end;
+    }
+    out.push_str(&src[last..]);
+    out
+}
+
+/// Applies non-overlapping `edits` to `src`.
+fn apply(src: &str, mut edits: Vec<(Range<usize>, String)>) -> String {
+    edits.sort_by_key(|(range, _)| range.start);
+    let mut out = String::new();
+    let mut last = 0;
+    for (range, text) in edits {
+        if range.start < last {
+            continue;
+        }
+        out.push_str(&src[last..range.start]);
+        out.push_str(&text);
+        last = range.end;
+    }
+    out.push_str(&src[last..]);
+    out
+}
+
+/// The source tree of cplit.
+pub struct Library {
+    modules: BTreeMap<ModPath, Source>,
+    /// `#[macro_export]` macro name to the defining module.
+    macros: BTreeMap<String, ModPath>,
+}
+
+impl Library {
+    /// Loads the library from its `src` directory.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let mut lib = Library {
+            modules: BTreeMap::new(),
+            macros: BTreeMap::new(),
+        };
+        lib.load_module(dir, vec![], &dir.join("lib.rs"))?;
+        for (path, module) in &lib.modules {
+            for name in &module.macros {
+                lib.macros.insert(name.clone(), path.clone());
+            }
+        }
+        Ok(lib)
+    }
+
+    fn load_module(&mut self, dir: &Path, path: ModPath, file: &Path) -> Result<(), String> {
+        let src = fs::read_to_string(file).map_err(|e| format!("{}: {}", file.display(), e))?;
+        let module = Source::new(strip(&src));
+        let children: Vec<_> = module.children.iter().map(|c| c.0.clone()).collect();
+        self.modules.insert(path.clone(), module);
+        for child in children {
+            let mut child_path = path.clone();
+            child_path.push(child);
+            let base = dir.join(child_path.join("/"));
+            let file = base.with_extension("rs");
+            let file = if file.exists() {
+                file
+            } else {
+                base.join("mod.rs")
+            };
+            self.load_module(dir, child_path, &file)?;
+        }
+        Ok(())
+    }
+
+    /// Turns `path` written in module `base` into a path from the crate root.
+    /// Returns `None` for external paths like `std::...`.
+    fn absolute(&self, base: &[String], path: &[String], roots: &[&str]) -> Option<ModPath> {
+        let (first, rest) = path.split_first()?;
+        let mut abs: ModPath = match first.as_str() {
+            s if roots.contains(&s) => vec![],
+            "self" => base.to_vec(),
+            "super" => base[..base.len() - 1].to_vec(),
+            s => {
+                let mut child = base.to_vec();
+                child.push(s.to_string());
+                if !self.modules.contains_key(&child) {
+                    return None;
+                }
+                child
+            }
+        };
+        for seg in rest {
+            if seg == "super" {
+                abs.pop();
+            } else {
+                abs.push(seg.clone());
+            }
+        }
+        Some(abs)
+    }
+
+    /// The longest prefix of `path` which is a module.
+    fn module_of(&self, path: &[String]) -> ModPath {
+        let mut cur = vec![];
+        for seg in path {
+            cur.push(seg.clone());
+            if !self.modules.contains_key(&cur) {
+                cur.pop();
+                break;
+            }
+        }
+        cur
+    }
+
+    /// The module defining the item at the absolute `path`, following re-exports.
+    fn origin(&self, path: &[String]) -> ModPath {
+        let module = self.module_of(path);
+        if let Some(seg) = path.get(module.len()) {
+            for u in self.modules[&module].uses.iter().filter(|u| u.public) {
+                for leaf in &u.leaves {
+                    let name = leaf.alias.as_ref().unwrap_or(leaf.path.last().unwrap());
+                    if name == seg {
+                        if let Some(target) = self.absolute(&module, &leaf.path, &["crate"]) {
+                            return self.origin(&target);
+                        }
+                    }
+                }
+            }
+        }
+        module
+    }
+
+    /// Resolves the absolute `path` and marks the modules it needs.
+    fn resolve(&self, path: &[String], need: &mut Vec<ModPath>) {
+        let mut cur: ModPath = vec![];
+        for (i, seg) in path.iter().enumerate() {
+            let mut child = cur.clone();
+            child.push(seg.clone());
+            if seg == "*" || seg == "self" {
+                break;
+            } else if self.modules.contains_key(&child) {
+                cur = child;
+                continue;
+            }
+            need.push(cur.clone());
+            if cur.is_empty() {
+                if let Some(module) = self.macros.get(seg) {
+                    need.push(module.clone());
+                    return;
+                }
+            }
+            // A re-exported item, follow it.
+            for u in self.modules[&cur].uses.iter().filter(|u| u.public) {
+                for leaf in &u.leaves {
+                    let name = leaf.alias.as_ref().unwrap_or(leaf.path.last().unwrap());
+                    if name == seg {
+                        if let Some(mut target) = self.absolute(&cur, &leaf.path, &["crate"]) {
+                            target.extend_from_slice(&path[i + 1..]);
+                            self.resolve(&target, need);
+                        }
+                    }
+                }
+            }
+            return;
+        }
+        // The path names a module, take it as a whole.
+        need.extend(self.modules.keys().filter(|k| k.starts_with(&cur)).cloned());
+    }
+
+    /// Paths referenced by the module at `path`.
+    fn references(&self, path: &[String]) -> Vec<ModPath> {
+        let module = &self.modules[path];
+        let idents = module.body_idents();
+        let mut refs = vec![];
+        for u in &module.uses {
+            for leaf in &u.leaves {
+                let name = leaf.alias.as_ref().unwrap_or(leaf.path.last().unwrap());
+                if !u.public || idents.contains(name.as_str()) {
+                    refs.extend(self.absolute(path, &leaf.path, &["crate"]));
+                }
+            }
+        }
+        for (_, p) in module.inline_paths(&["crate", "$crate", "self", "super"]) {
+            refs.extend(self.absolute(path, &p, &["crate", "$crate"]));
+        }
+        for name in module.macro_calls() {
+            if self.macros.contains_key(&name) {
+                refs.push(vec![name]);
+            }
+        }
+        refs
+    }
+
+    /// Computes the modules needed by the absolute `paths`, with their ancestors.
+    fn closure(&self, paths: Vec<ModPath>) -> BTreeSet<ModPath> {
+        let mut included = BTreeSet::new();
+        let mut queue = vec![];
+        for p in paths {
+            self.resolve(&p, &mut queue);
+        }
+        while let Some(m) = queue.pop() {
+            for k in 0..=m.len() {
+                let ancestor = m[..k].to_vec();
+                if included.insert(ancestor.clone()) {
+                    for r in self.references(&ancestor) {
+                        self.resolve(&r, &mut queue);
+                    }
+                }
+            }
+        }
+        included
+    }
+
+    /// Rewrites a path from the root of cplit into a path inside the bundled `cplit` module.
+    fn rewrite(&self, path: &[String]) -> Vec<String> {
+        let mut out = vec![path[0].clone()];
+        if path.len() > 1 && !self.macros.contains_key(&path[1]) {
+            out.push("cplit".to_string());
+        }
+        out.extend_from_slice(&path[1..]);
+        out
+    }
+
+    /// Renders the module at `path` with only the `included` children inlined.
+    fn render(&self, path: &[String], included: &BTreeSet<ModPath>) -> String {
+        let module = &self.modules[path];
+        let mut edits = vec![];
+        for (name, item, semi) in &module.children {
+            let mut child = path.to_vec();
+            child.push(name.clone());
+            if included.contains(&child) {
+                let inner = self.render(&child, included);
+                edits.push((semi.clone(), format!(" {{\n{}\n}}", inner.trim())));
+            } else {
+                edits.push((item.clone(), String::new()));
+            }
+        }
+        for u in &module.uses {
+            let leaves: Vec<_> = u
+                .leaves
+                .iter()
+                .filter(|leaf| {
+                    !u.public
+                        || self
+                            .absolute(path, &leaf.path, &["crate"])
+                            .map_or(true, |abs| included.contains(&self.origin(&abs)))
+                })
+                .map(|leaf| UseLeaf {
+                    path: match leaf.path[0].as_str() {
+                        "crate" => self.rewrite(&leaf.path),
+                        _ => leaf.path.clone(),
+                    },
+                    alias: leaf.alias.clone(),
+                })
+                .collect();
+            if leaves.is_empty() {
+                edits.push((u.item.clone(), String::new()));
+            } else if leaves.len() < u.leaves.len() || u.leaves.iter().any(|l| l.path[0] == "crate")
+            {
+                edits.push((u.tree.clone(), render_use_tree(&leaves)));
+            }
+        }
+        for (i, p) in module.inline_paths(&["crate", "$crate"]) {
+            if p.len() > 1 && !self.macros.contains_key(&p[1]) {
+                let sep = module.tokens[i + 1].span.clone();
+                edits.push((sep.end..sep.end, "cplit::".to_string()));
+            }
+        }
+        apply(&module.src, edits)
+    }
+
+    /// Bundles the solution `src` with the modules of cplit it uses.
+    pub fn bundle(&self, src: &str) -> String {
+        let solution = Source::new(src.to_string());
+        let mut paths = vec![];
+        for u in &solution.uses {
+            for leaf in u.leaves.iter().filter(|leaf| leaf.path[0] == "cplit") {
+                paths.push(leaf.path[1..].to_vec());
+            }
+        }
+        for (_, p) in solution.inline_paths(&["cplit"]) {
+            paths.push(p[1..].to_vec());
+        }
+        for name in solution.macro_calls() {
+            if self.macros.contains_key(&name) {
+                paths.push(vec![name]);
+            }
+        }
+        let included = self.closure(paths);
+
+        let mut edits = vec![];
+        // `extern crate cplit;`
+        for i in 0..solution.tokens.len() {
+            if solution.joined(i..i + 4) == "extern crate cplit ;" {
+                let start = solution.item_start(i);
+                edits.push((
+                    solution.tokens[start].span.start..solution.tokens[i + 3].span.end,
+                    String::new(),
+                ));
+            }
+        }
+        for u in solution
+            .uses
+            .iter()
+            .filter(|u| u.leaves.iter().any(|l| l.path[0] == "cplit"))
+        {
+            let leaves: Vec<_> = u
+                .leaves
+                .iter()
+                .filter(|leaf| {
+                    // Macros are already defined at the root.
+                    !(u.depth == 0
+                        && leaf.path[0] == "cplit"
+                        && leaf.path.len() == 2
+                        && self.macros.contains_key(&leaf.path[1]))
+                })
+                .map(|leaf| UseLeaf {
+                    path: match leaf.path[0].as_str() {
+                        "cplit" => {
+                            self.rewrite(&[&["crate".to_string()], &leaf.path[1..]].concat())
+                        }
+                        _ => leaf.path.clone(),
+                    },
+                    alias: leaf.alias.clone(),
+                })
+                .collect();
+            if leaves.is_empty() {
+                edits.push((u.item.clone(), String::new()));
+            } else {
+                edits.push((u.tree.clone(), render_use_tree(&leaves)));
+            }
+        }
+        for (i, p) in solution.inline_paths(&["cplit"]) {
+            let mut start = solution.tokens[i].span.start;
+            if i > 0 && solution.tokens[i - 1].kind == Kind::PathSep {
+                start = solution.tokens[i - 1].span.start;
+            }
+            let path = self.rewrite(&[&["crate".to_string()], &p[1..2]].concat());
+            edits.push((
+                start..solution.tokens[i + 1].span.start,
+                path[..path.len() - 1].join("::"),
+            ));
+        }
+
+        let mut lib = self.render(&[], &included);
+        while lib.contains("\n\n\n") {
+            lib = lib.replace("\n\n\n", "\n\n");
+        }
+        format!(
+            "{}\n\n#[allow(dead_code, unused_imports, unused_macros)]\npub mod cplit {{\n{}}}\n",
+            apply(src, edits).trim_end(),
+            lib.trim(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bundle::Library;
+    use std::path::Path;
+    use std::process::Command;
+
+    #[test]
+    fn segment_tree_and_macros() {
+        let lib = Library::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src")).unwrap();
+        let solution = r#"
+use cplit::data_structure::{segment_tree::AddSum, SegmentTree};
+use cplit::{printv, scanln};
+
+fn main() {
+    let n: usize;
+    scanln!(n);
+    let v: Vec<i64>;
+    scanln!(v; n);
+    let st = SegmentTree::<i64, i64, AddSum>::from(v);
+    printv!(st.len(), <i64 as cplit::num::Zero>::ZERO);
+}
+"#;
+        let out = lib.bundle(solution);
+        assert!(out.contains("pub mod segment_tree {"));
+        assert!(out.contains("$crate::cplit::io::Scanner::new"));
+        assert!(!out.contains("mod disjoint_set_union"));
+        assert!(!out.contains("mod graph"));
+        assert!(!out.contains("#[cfg(test)]"));
+        assert!(!out.contains("///"));
+
+        compile("segment-tree", out);
+    }
+
+    #[test]
+    fn matrix_inverse() {
+        let lib = Library::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src")).unwrap();
+        let solution = r#"
+use cplit::linalg::Matrix;
+use cplit::num::Rational;
+
+fn main() {
+    let a = Matrix::from(vec![
+        vec![Rational::from(0); 3],
+        vec![Rational::from(0), Rational::from(2), Rational::from(1)],
+        vec![Rational::from(0), Rational::from(1), Rational::from(1)],
+    ]);
+    let inv = a.inv().unwrap();
+    assert!(&a / &a == &inv * &a);
+}
+"#;
+        let out = lib.bundle(solution);
+        assert!(out.contains("mod gauss"));
+        compile("matrix", out);
+    }
+
+    /// Compiles the bundled source with rustc, in a temporary directory named after `name`.
+    fn compile(name: &str, out: String) {
+        let dir = std::env::temp_dir().join(format!("cplit-bundle-test-{}", name));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.rs"), out).unwrap();
+        let status = Command::new("rustc")
+            .args(["--edition", "2021", "--crate-type", "bin", "-o"])
+            .arg(dir.join("main"))
+            .arg(dir.join("main.rs"))
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+}
@@ -0,0 +1,154 @@
+//! A minimal Rust lexer, just enough to find paths, items and comments.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Identifiers, keywords, numbers, lifetimes and `$crate`.
+    Ident,
+    /// `::`
+    PathSep,
+    /// Any other single punctuation character.
+    Punct,
+    /// String, byte string and char literals.
+    Literal,
+    /// `///` and `//!` comments, including the line break.
+    DocComment,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: Kind,
+    pub span: Range<usize>,
+}
+
+/// Splits `src` into tokens, dropping whitespaces and regular comments.
+pub fn tokenize(src: &str) -> Vec<Token> {
+    let s = src.as_bytes();
+    let is_ident = |c: u8| c.is_ascii_alphanumeric() || c == b'_' || c >= 0x80;
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < s.len() {
+        let start = i;
+        let c = s[i];
+        let kind = if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if s[i..].starts_with(b"//") {
+            let end = s[i..]
+                .iter()
+                .position(|&c| c == b'\n')
+                .map_or(s.len(), |p| i + p + 1);
+            let doc = (s[i..].starts_with(b"///") && !s[i..].starts_with(b"////"))
+                || s[i..].starts_with(b"//!");
+            i = end;
+            if doc {
+                Kind::DocComment
+            } else {
+                continue;
+            }
+        } else if s[i..].starts_with(b"/*") {
+            let mut depth = 0;
+            while i < s.len() {
+                if s[i..].starts_with(b"/*") {
+                    depth += 1;
+                    i += 2;
+                } else if s[i..].starts_with(b"*/") {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+            continue;
+        } else if let Some(len) = raw_string(&s[i..]) {
+            i += len;
+            Kind::Literal
+        } else if c == b'"' || (c == b'b' && s.get(i + 1) == Some(&b'"')) {
+            i += if c == b'b' { 2 } else { 1 };
+            while s[i] != b'"' {
+                i += if s[i] == b'\\' { 2 } else { 1 };
+            }
+            i += 1;
+            Kind::Literal
+        } else if c == b'\'' || (c == b'b' && s.get(i + 1) == Some(&b'\'')) {
+            let j = if c == b'b' { i + 1 } else { i };
+            if s[j + 1] == b'\\' {
+                i = j + 2;
+                while s[i] != b'\'' {
+                    i += if s[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+                Kind::Literal
+            } else {
+                let len = utf8_len(s[j + 1]);
+                if s.get(j + 1 + len) == Some(&b'\'') {
+                    i = j + 2 + len;
+                    Kind::Literal
+                } else {
+                    // A lifetime or a label.
+                    i = j + 1;
+                    while i < s.len() && is_ident(s[i]) {
+                        i += 1;
+                    }
+                    Kind::Ident
+                }
+            }
+        } else if is_ident(c) || (c == b'$' && s.get(i + 1).is_some_and(|&c| is_ident(c))) {
+            i += 1;
+            while i < s.len() && is_ident(s[i]) {
+                i += 1;
+            }
+            Kind::Ident
+        } else if s[i..].starts_with(b"::") {
+            i += 2;
+            Kind::PathSep
+        } else {
+            i += utf8_len(c);
+            Kind::Punct
+        };
+        tokens.push(Token {
+            kind,
+            span: start..i,
+        });
+    }
+    tokens
+}
+
+fn utf8_len(c: u8) -> usize {
+    match c.leading_ones() {
+        0 => 1,
+        n => n as usize,
+    }
+}
+
+/// Returns the length of the raw string literal at the start of `s`, if any.
+fn raw_string(s: &[u8]) -> Option<usize> {
+    let mut i = match s {
+        [b'r', b'#' | b'"', ..] => 1,
+        [b'b', b'r', b'#' | b'"', ..] => 2,
+        _ => return None,
+    };
+    let hashes = s[i..].iter().take_while(|&&c| c == b'#').count();
+    i += hashes;
+    if s.get(i) != Some(&b'"') {
+        return None;
+    }
+    i += 1;
+    loop {
+        if s[i] == b'"'
+            && s[i + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|&&c| c == b'#')
+                .count()
+                == hashes
+        {
+            return Some(i + 1 + hashes);
+        }
+        i += 1;
+    }
+}
@@ -0,0 +1,71 @@
+//! # cargo-cplit
+//!
+//! Online judges accept a single source file, so this binary bundles a solution
+//! with the modules of cplit it uses.
+//!
+//! ```text
+//! cargo cplit bundle <SOLUTION> [-o <OUTPUT>] [--src <CPLIT_SRC>]
+//! ```
+//!
+//! - `use cplit::...` paths are resolved, and the needed modules are inlined transitively
+//!   into a `pub mod cplit { ... }` appended to the solution.
+//! - `#[macro_export]` macros are kept at the crate root, and `$crate` paths are rewritten.
+//! - Tests and doc comments are stripped.
+//!
+//! By default, the sources this binary was built from are used.
+
+mod bundle;
+mod lexer;
+
+use bundle::Library;
+use std::path::PathBuf;
+use std::process::exit;
+
+const USAGE: &str = "Usage: cargo cplit bundle <SOLUTION> [-o <OUTPUT>] [--src <CPLIT_SRC>]";
+
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+    // Invoked as `cargo cplit ...`.
+    if args.peek().map(String::as_str) == Some("cplit") {
+        args.next();
+    }
+    if args.next().as_deref() != Some("bundle") {
+        eprintln!("{}", USAGE);
+        exit(2);
+    }
+
+    let mut input = None;
+    let mut output = None;
+    let mut src = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src"));
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => output = args.next(),
+            "--src" => src = args.next().map(PathBuf::from).unwrap_or(src),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => input = Some(arg),
+        }
+    }
+    let Some(input) = input else {
+        eprintln!("{}", USAGE);
+        exit(2);
+    };
+
+    let result = Library::load(&src).and_then(|lib| {
+        let solution = std::fs::read_to_string(&input).map_err(|e| format!("{}: {}", input, e))?;
+        let bundled = lib.bundle(&solution);
+        match &output {
+            Some(path) => std::fs::write(path, bundled).map_err(|e| format!("{}: {}", path, e)),
+            None => {
+                print!("{}", bundled);
+                Ok(())
+            }
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        exit(1);
+    }
+}
@@ -0,0 +1,373 @@
+use crate::data_structure::to_range;
+use crate::num::{Numeric, NumericAssOps, NumericCmpOps, NumericOps};
+use std::ops::RangeBounds;
+use std::vec;
+
+macro_rules! low_bit {
+    ($index: expr) => {
+        ($index & (!$index + 1))
+    };
+}
+
+pub mod multiset;
+pub mod range;
+pub mod two_dim;
+#[doc(inline)]
+pub use self::multiset::OrderStatisticMultiset;
+#[doc(inline)]
+pub use self::range::RangeBinaryIndexedTree;
+#[doc(inline)]
+pub use self::two_dim::{BinaryIndexedTree2D, RangeBinaryIndexedTree2D};
+
+/// Binary indexed tree (Fenwick tree) for range sum queries and point updates.
+///
+/// For more information, see [Fenwick tree](https://cp-algorithms.com/data_structures/fenwick.html).
+///
+/// # Examples
+/// ```no_run
+/// use cplit::data_structure::BinaryIndexedTree;
+/// use cplit::scanln;
+///
+/// fn main() {
+///     let (n, m): (usize, usize);
+///     scanln!(n, m);
+///     let v: Vec<isize>;
+///     scanln!(v; n);
+///     let mut bit = BinaryIndexedTree::from(v);
+///     for _ in 0..m {
+///         let (op, x, y): (usize, usize, isize);
+///         scanln!(op, x, y);
+///         match op {
+///             1 => {
+///                 bit.add(x, y);
+///             }
+///             2 => {
+///                 println!("{}", bit.sum(x..=y as usize));
+///             }
+///             _ => unreachable!(),
+///         }
+///     }
+/// }
+#[derive(Debug)]
+pub struct BinaryIndexedTree<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    body: Vec<N>,
+}
+
+impl<N> BinaryIndexedTree<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    /// Constructs an empty binary indexed tree.
+    pub fn new() -> Self {
+        Self {
+            body: vec![N::ZERO],
+        }
+    }
+
+    /// Constructs an empty binary indexed tree with the specified `capacity`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut body = Vec::with_capacity(capacity + 1);
+        body.push(N::ZERO);
+        Self { body }
+    }
+
+    /// The length of the binary indexed tree.
+    pub fn len(&self) -> usize {
+        self.body.len() - 1
+    }
+
+    /// Returns `true` if the binary indexed tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Updates the value of the element at `index` by adding `delta`.
+    /// Complexity: _O(log n)_.
+    pub fn add(&mut self, mut index: usize, delta: N) {
+        self.check(index);
+        while index <= self.len() {
+            self.body[index] += delta.clone();
+            index += low_bit!(index);
+        }
+    }
+
+    /// Returns the sum of the elements in the range `bounds`.
+    /// Complexity: _O(log n)_.
+    pub fn sum(&self, bounds: impl RangeBounds<usize>) -> N {
+        let (mut start, mut end) = to_range(bounds, self.len());
+        if start >= end {
+            return N::ZERO;
+        }
+
+        start -= 1;
+        end -= 1;
+
+        let mut s = N::ZERO;
+        while end > start {
+            s += self.body[end].clone();
+            end -= low_bit!(end);
+        }
+        while start > end {
+            s -= self.body[start].clone();
+            start -= low_bit!(start);
+        }
+        s
+    }
+
+    /// Returns the smallest index `i` such that `sum(..=i) >= target`, or `len + 1` if there is none.
+    ///
+    /// All the elements must be non-negative.
+    /// Complexity: _O(log n)_.
+    pub fn lower_bound(&self, target: N) -> usize
+    where
+        N: NumericCmpOps,
+    {
+        let (mut pos, mut rest) = (0, target);
+        let mut step = match self.len() {
+            0 => 0,
+            len => 1 << (usize::BITS - 1 - len.leading_zeros()),
+        };
+        while step > 0 {
+            if pos + step <= self.len() && self.body[pos + step] < rest {
+                pos += step;
+                rest -= self.body[pos].clone();
+            }
+            step >>= 1;
+        }
+        pos + 1
+    }
+
+    /// Returns the index of the `k`-th smallest element (1-based) when the binary indexed tree
+    /// counts the occurrences of each index, or `None` if there are fewer than `k`.
+    ///
+    /// All the elements must be non-negative.
+    /// Complexity: _O(log n)_.
+    pub fn kth(&self, k: N) -> Option<usize>
+    where
+        N: NumericCmpOps,
+    {
+        if k <= N::ZERO {
+            return None;
+        }
+        let index = self.lower_bound(k);
+        (index <= self.len()).then_some(index)
+    }
+
+    /// Pushes a new element into the binary indexed tree.
+    /// Complexity: _O(log n)_.
+    pub fn push(&mut self, value: N) {
+        let len = self.len() + 1;
+        let sum = self.sum(len - low_bit!(len) + 1..len);
+        self.body.push(value + sum);
+    }
+
+    /// Pops the last element from the binary indexed tree.
+    /// Complexity: _O(log n)_.
+    pub fn pop(&mut self) -> Option<N> {
+        if self.is_empty() {
+            return None;
+        }
+        let value = self.get(self.len());
+        self.body.pop();
+        Some(value)
+    }
+
+    /// Returns the element at `index`.
+    /// Complexity: _O(log n)_, and _O(1)_ amortized over all the indices.
+    pub fn get(&self, index: usize) -> N {
+        self.check(index);
+        // The children of node `index` cover all but the last element of its range.
+        let mut value = self.body[index].clone();
+        let mut step = 1;
+        while step < low_bit!(index) {
+            value -= self.body[index - step].clone();
+            step <<= 1;
+        }
+        value
+    }
+
+    /// Sets the element at `index` to `value`.
+    /// Complexity: _O(log n)_.
+    pub fn set(&mut self, index: usize, value: N) {
+        let old = self.get(index);
+        self.add(index, value - old);
+    }
+
+    /// Returns an iterator over the elements.
+    /// Complexity: _O(n)_ in total.
+    pub fn iter(&self) -> impl Iterator<Item = N> + '_ {
+        (1..=self.len()).map(move |i| self.get(i))
+    }
+
+    fn check(&self, index: usize) {
+        if !(1..=self.len()).contains(&index) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len(),
+                index
+            );
+        }
+    }
+
+    // Initializes the binary indexed tree.
+    fn init(&mut self) {
+        for i in 1..=self.len() {
+            let j = i + low_bit!(i);
+            if j <= self.len() {
+                let t = self.body[i].clone();
+                self.body[j] += t;
+            }
+        }
+    }
+}
+
+impl<N> From<Vec<N>> for BinaryIndexedTree<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    /// Constructs a new binary indexed tree from a 1-based vector.
+    ///
+    /// Complexity: _O(n)_.
+    fn from(a: Vec<N>) -> Self {
+        let mut bit = BinaryIndexedTree { body: a };
+        bit.init();
+        bit
+    }
+}
+
+impl<N> From<&[N]> for BinaryIndexedTree<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    /// Constructs a new binary indexed tree from a 1-based slice.
+    ///
+    /// Complexity: _O(n)_.
+    fn from(a: &[N]) -> Self {
+        Self::from(a.to_vec())
+    }
+}
+
+impl<N> From<BinaryIndexedTree<N>> for Vec<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    /// Converts the binary indexed tree back into a 1-based vector of its elements.
+    ///
+    /// Complexity: _O(n)_.
+    fn from(bit: BinaryIndexedTree<N>) -> Self {
+        let mut a = bit.body;
+        a[0] = N::ZERO;
+        for i in (1..a.len()).rev() {
+            let j = i + low_bit!(i);
+            if j < a.len() {
+                let t = a[i].clone();
+                a[j] -= t;
+            }
+        }
+        a
+    }
+}
+
+impl<N> Extend<N> for BinaryIndexedTree<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    fn extend<I: IntoIterator<Item = N>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<N> Default for BinaryIndexedTree<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::BinaryIndexedTree;
+    use crate::fscanln;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p3374() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+5 5
+1 5 4 2 3
+1 1 3
+2 2 5
+1 3 -1
+1 4 2
+2 1 4
+"#,
+        ));
+
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let v: Vec<isize>;
+        fscanln!(reader, v; n);
+        let mut bit = BinaryIndexedTree::from(v);
+        let mut ans: Vec<_> = vec![];
+        for _ in 0..m {
+            let (op, x, y): (usize, usize, isize);
+            fscanln!(reader, op, x, y);
+            match op {
+                1 => {
+                    bit.add(x, y);
+                }
+                2 => {
+                    ans.push(bit.sum(x..=y as usize));
+                }
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(ans, vec![14, 16]);
+    }
+
+    #[test]
+    fn lower_bound() {
+        let v = vec![0_u32, 2, 0, 1, 3, 0, 0, 4];
+        let bit = BinaryIndexedTree::from(v.clone());
+        for target in 0..=12 {
+            let expected = (1..v.len())
+                .find(|&i| v[1..=i].iter().sum::<u32>() >= target)
+                .unwrap_or(v.len());
+            assert_eq!(bit.lower_bound(target), expected);
+        }
+        assert_eq!(bit.kth(0), None);
+        assert_eq!(bit.kth(1), Some(1));
+        assert_eq!(bit.kth(3), Some(3));
+        assert_eq!(bit.kth(6), Some(4));
+        assert_eq!(bit.kth(7), Some(7));
+        assert_eq!(bit.kth(11), None);
+        assert_eq!(BinaryIndexedTree::<u32>::new().lower_bound(1), 1);
+    }
+
+    #[test]
+    fn get_set_and_round_trip() {
+        let mut bit = BinaryIndexedTree::from(vec![0_i64, 3, -1, 4, 1, -5]);
+        assert_eq!(bit.iter().collect::<Vec<_>>(), vec![3, -1, 4, 1, -5]);
+        bit.set(2, 7);
+        bit.add(4, 2);
+        assert_eq!((bit.get(2), bit.get(4), bit.sum(2..=4)), (7, 3, 14));
+        bit.extend([2, 6, -3]);
+        assert_eq!((bit.len(), bit.sum(..), bit.sum(5..)), (8, 17, 0));
+        assert_eq!((bit.pop(), bit.pop()), (Some(-3), Some(6)));
+        bit.push(10);
+        assert_eq!(bit.sum(6..), 12);
+        let v = vec![0, 3, 7, 4, 3, -5, 2, 10];
+        let round_trip: Vec<i64> = bit.into();
+        assert_eq!(round_trip, v);
+        assert_eq!(Vec::from(BinaryIndexedTree::from(&v[..])), v);
+        assert_eq!(BinaryIndexedTree::<i64>::new().pop(), None);
+    }
+}
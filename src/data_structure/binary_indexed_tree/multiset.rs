@@ -0,0 +1,205 @@
+use crate::data_structure::BinaryIndexedTree;
+
+/// Multiset with order statistics over a universe of values known in advance.
+///
+/// The universe is coordinate-compressed, and a [`BinaryIndexedTree`] counts the occurrences
+/// of each value, so that every operation takes _O(log n)_ for a universe of `n` values.
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::binary_indexed_tree::OrderStatisticMultiset;
+///
+/// let mut set = OrderStatisticMultiset::new(vec![30, 10, 20, 10]);
+/// set.insert(20);
+/// set.insert(10);
+/// set.insert(20);
+/// assert_eq!(set.len(), 3);
+/// assert_eq!(set.kth(2), Some(&20));
+/// assert_eq!(set.rank(&15), 1);
+/// assert!(set.remove(&20));
+/// assert_eq!(set.count(&20), 1);
+/// assert_eq!(set.kth(3), None);
+/// ```
+#[derive(Debug)]
+pub struct OrderStatisticMultiset<T>
+where
+    T: Ord,
+{
+    /// The sorted distinct values of the universe.
+    keys: Vec<T>,
+    count: BinaryIndexedTree<isize>,
+    len: usize,
+}
+
+impl<T> OrderStatisticMultiset<T>
+where
+    T: Ord,
+{
+    /// Constructs an empty multiset which can hold the values in `universe`.
+    ///
+    /// Complexity: _O(n log n)_.
+    pub fn new(universe: impl IntoIterator<Item = T>) -> Self {
+        let mut keys: Vec<T> = universe.into_iter().collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let count = BinaryIndexedTree::from(vec![0; keys.len() + 1]);
+        Self {
+            keys,
+            count,
+            len: 0,
+        }
+    }
+
+    /// The number of elements in the multiset, counting multiplicity.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the multiset is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts `value` into the multiset.
+    ///
+    /// Panics if `value` is not in the universe.
+    /// Complexity: _O(log n)_.
+    pub fn insert(&mut self, value: T) {
+        match self.index(&value) {
+            Some(index) => {
+                self.count.add(index, 1);
+                self.len += 1;
+            }
+            None => panic!("Value out of the universe of the multiset"),
+        }
+    }
+
+    /// Removes one occurrence of `value` from the multiset.
+    /// Returns whether `value` was present.
+    /// Complexity: _O(log n)_.
+    pub fn remove(&mut self, value: &T) -> bool {
+        match self.index(value) {
+            Some(index) if self.count.sum(index..=index) > 0 => {
+                self.count.add(index, -1);
+                self.len -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the number of occurrences of `value`.
+    /// Complexity: _O(log n)_.
+    pub fn count(&self, value: &T) -> usize {
+        self.index(value)
+            .map_or(0, |index| self.count.sum(index..=index) as usize)
+    }
+
+    /// Returns `true` if the multiset contains `value`.
+    /// Complexity: _O(log n)_.
+    pub fn contains(&self, value: &T) -> bool {
+        self.count(value) > 0
+    }
+
+    /// Returns the number of elements less than `value`, which need not be in the universe.
+    /// Complexity: _O(log n)_.
+    pub fn rank(&self, value: &T) -> usize {
+        let index = self.keys.partition_point(|key| key < value);
+        self.count.sum(..=index) as usize
+    }
+
+    /// Returns the `k`-th smallest element (1-based), or `None` if there are fewer than `k`.
+    /// Complexity: _O(log n)_.
+    pub fn kth(&self, k: usize) -> Option<&T> {
+        self.count
+            .kth(k as isize)
+            .map(|index| &self.keys[index - 1])
+    }
+
+    /// The 1-based index of `value` in the universe.
+    fn index(&self, value: &T) -> Option<usize> {
+        self.keys.binary_search(value).ok().map(|index| index + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::binary_indexed_tree::OrderStatisticMultiset;
+    use crate::fscanln;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p3369() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+10
+1 106465
+4 1
+1 317721
+1 460929
+1 644985
+1 84185
+1 89851
+6 81968
+1 492737
+5 493598
+"#,
+        ));
+
+        let n: usize;
+        fscanln!(reader, n);
+        let mut ops = vec![];
+        for _ in 0..n {
+            let (op, x): (usize, i64);
+            fscanln!(reader, op, x);
+            ops.push((op, x));
+        }
+        let universe = ops.iter().filter(|&&(op, _)| op == 1).map(|&(_, x)| x);
+        let mut set = OrderStatisticMultiset::new(universe);
+        let mut ans = vec![];
+        for (op, x) in ops {
+            match op {
+                1 => set.insert(x),
+                2 => assert!(set.remove(&x)),
+                3 => ans.push(set.rank(&x) as i64 + 1),
+                4 => ans.push(*set.kth(x as usize).unwrap()),
+                5 => ans.push(*set.kth(set.rank(&x)).unwrap()),
+                6 => ans.push(*set.kth(set.rank(&x) + set.count(&x) + 1).unwrap()),
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(ans, vec![106465, 84185, 492737]);
+    }
+
+    #[test]
+    fn duplicates_and_missing_values() {
+        let mut set = OrderStatisticMultiset::new([10, 30, 20, 30, 50]);
+        for x in [30, 30, 10, 50] {
+            set.insert(x);
+        }
+        assert_eq!(set.len(), 4);
+        assert_eq!((set.count(&30), set.count(&20), set.count(&25)), (2, 0, 0));
+        assert!(!set.contains(&20));
+        // Ranks of values outside the universe fall between its keys.
+        assert_eq!([5, 10, 30, 31, 100].map(|x| set.rank(&x)), [0, 0, 1, 3, 4]);
+        assert_eq!(
+            (0..=5).map(|k| set.kth(k).copied()).collect::<Vec<_>>(),
+            vec![None, Some(10), Some(30), Some(30), Some(50), None]
+        );
+        assert!(!set.remove(&20));
+        assert!(!set.remove(&25));
+        assert!(set.remove(&30));
+        assert!(set.remove(&10));
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            (set.kth(1), set.kth(2), set.kth(3)),
+            (Some(&30), Some(&50), None)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of the universe of the multiset")]
+    fn insert_outside_universe() {
+        OrderStatisticMultiset::new([1, 2, 3]).insert(4);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::data_structure::{to_range, BinaryIndexedTree};
+use crate::num::{Numeric, NumericAssOps, NumericOps};
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+
+/// Binary indexed tree for range sum queries and range updates.
+///
+/// It keeps the differences `d[i] = a[i] - a[i - 1]` in one tree and `d[i] * (i - 1)` in another,
+/// so that the prefix sum `a[1] + ... + a[x]` is `x * sum(d[..=x]) - sum(d[i] * (i - 1))`.
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::binary_indexed_tree::RangeBinaryIndexedTree;
+///
+/// let mut bit = RangeBinaryIndexedTree::from(vec![0_i64, 1, 5, 4, 2, 3]);
+/// bit.add(2..=4, 2);
+/// assert_eq!(bit.sum(3..=4), 10);
+/// bit.add(.., -1);
+/// assert_eq!(bit.sum(..), 16);
+/// assert_eq!(bit.get(1), 0);
+/// ```
+#[derive(Debug)]
+pub struct RangeBinaryIndexedTree<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    diff: BinaryIndexedTree<N>,
+    weighted: BinaryIndexedTree<N>,
+}
+
+impl<N> RangeBinaryIndexedTree<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone + TryFrom<usize>,
+    <N as TryFrom<usize>>::Error: Debug,
+{
+    /// Constructs a binary indexed tree of `len` zeros.
+    pub fn with_len(len: usize) -> Self {
+        Self::from(vec![N::ZERO; len + 1])
+    }
+
+    /// The length of the binary indexed tree.
+    pub fn len(&self) -> usize {
+        self.diff.len()
+    }
+
+    /// Returns `true` if the binary indexed tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds `delta` to every element in the range `bounds`.
+    /// Complexity: _O(log n)_.
+    pub fn add(&mut self, bounds: impl RangeBounds<usize>, delta: N) {
+        let (start, end) = to_range(bounds, self.len());
+        if start >= end {
+            return;
+        }
+        self.diff.add(start, delta.clone());
+        self.weighted
+            .add(start, delta.clone() * N::try_from(start - 1).unwrap());
+        if end <= self.len() {
+            self.diff.add(end, N::ZERO - delta.clone());
+            self.weighted
+                .add(end, N::ZERO - delta * N::try_from(end - 1).unwrap());
+        }
+    }
+
+    /// Returns the element at `index`.
+    /// Complexity: _O(log n)_.
+    pub fn get(&self, index: usize) -> N {
+        if !(1..=self.len()).contains(&index) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len(),
+                index
+            );
+        }
+        self.diff.sum(..=index)
+    }
+
+    /// Returns the sum of the elements in the range `bounds`.
+    /// Complexity: _O(log n)_.
+    pub fn sum(&self, bounds: impl RangeBounds<usize>) -> N {
+        let (start, end) = to_range(bounds, self.len());
+        if start >= end {
+            return N::ZERO;
+        }
+        self.prefix(end - 1) - self.prefix(start - 1)
+    }
+
+    /// The sum of the first `x` elements.
+    fn prefix(&self, x: usize) -> N {
+        self.diff.sum(..=x) * N::try_from(x).unwrap() - self.weighted.sum(..=x)
+    }
+}
+
+impl<N, T> From<T> for RangeBinaryIndexedTree<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone + TryFrom<usize>,
+    <N as TryFrom<usize>>::Error: Debug,
+    T: Into<Vec<N>>,
+{
+    /// Constructs a new binary indexed tree from a 1-based collection.
+    ///
+    /// Complexity: _O(n)_.
+    fn from(a: T) -> Self {
+        let a = a.into();
+        let mut diff = vec![N::ZERO; a.len()];
+        let mut weighted = vec![N::ZERO; a.len()];
+        for i in 1..a.len() {
+            let prev = if i > 1 { a[i - 1].clone() } else { N::ZERO };
+            diff[i] = a[i].clone() - prev;
+            weighted[i] = diff[i].clone() * N::try_from(i - 1).unwrap();
+        }
+        Self {
+            diff: BinaryIndexedTree::from(diff),
+            weighted: BinaryIndexedTree::from(weighted),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::binary_indexed_tree::RangeBinaryIndexedTree;
+    use crate::fscanln;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p3372() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+5 5
+1 5 4 2 3
+2 2 4
+1 2 3 2
+2 3 4
+1 1 5 1
+2 1 4
+"#,
+        ));
+
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let v: Vec<i64>;
+        fscanln!(reader, v; n);
+        let mut bit = RangeBinaryIndexedTree::from(v);
+        let mut ans = vec![];
+        for _ in 0..m {
+            let (op, x, y, k): (usize, usize, usize, i64);
+            fscanln!(reader, op, x, y, k; ?);
+            match op {
+                1 => bit.add(x..=y, k),
+                2 => ans.push(bit.sum(x..=y)),
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(ans, vec![11, 8, 20]);
+    }
+}
@@ -0,0 +1,326 @@
+use crate::data_structure::to_range;
+use crate::num::{Numeric, NumericAssOps, NumericOps};
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+
+/// Two-dimensional binary indexed tree for rectangle sum queries and point updates.
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::binary_indexed_tree::BinaryIndexedTree2D;
+///
+/// let mut bit = BinaryIndexedTree2D::from(vec![
+///     vec![0_i64, 0, 0, 0],
+///     vec![0, 1, 2, 3],
+///     vec![0, 4, 5, 6],
+/// ]);
+/// assert_eq!(bit.sum(.., 2..), 16);
+/// bit.add(2, 1, 10);
+/// assert_eq!(bit.sum(2..=2, ..=2), 19);
+/// ```
+#[derive(Debug)]
+pub struct BinaryIndexedTree2D<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    body: Vec<Vec<N>>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<N> BinaryIndexedTree2D<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    /// Constructs a binary indexed tree of `rows` by `cols` zeros.
+    pub fn with_size(rows: usize, cols: usize) -> Self {
+        Self {
+            body: vec![vec![N::ZERO; cols + 1]; rows + 1],
+            rows,
+            cols,
+        }
+    }
+
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Updates the value of the element at `(row, col)` by adding `delta`.
+    /// Complexity: _O(log n log m)_.
+    pub fn add(&mut self, row: usize, col: usize, delta: N) {
+        if !(1..=self.rows).contains(&row) || !(1..=self.cols).contains(&col) {
+            panic!(
+                "Index out of bounds: the size is {}x{} but the index is ({}, {})",
+                self.rows, self.cols, row, col
+            );
+        }
+        let mut i = row;
+        while i <= self.rows {
+            let mut j = col;
+            while j <= self.cols {
+                self.body[i][j] += delta.clone();
+                j += low_bit!(j);
+            }
+            i += low_bit!(i);
+        }
+    }
+
+    /// Returns the sum of the elements in the rectangle `rows` by `cols`.
+    /// Complexity: _O(log n log m)_.
+    pub fn sum(&self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> N {
+        let (top, bottom) = to_range(rows, self.rows);
+        let (left, right) = to_range(cols, self.cols);
+        if top >= bottom || left >= right {
+            return N::ZERO;
+        }
+        self.prefix(bottom - 1, right - 1)
+            - self.prefix(top - 1, right - 1)
+            - self.prefix(bottom - 1, left - 1)
+            + self.prefix(top - 1, left - 1)
+    }
+
+    /// The sum of the rectangle `1..=row` by `1..=col`.
+    fn prefix(&self, row: usize, col: usize) -> N {
+        let mut s = N::ZERO;
+        let mut i = row;
+        while i > 0 {
+            let mut j = col;
+            while j > 0 {
+                s += self.body[i][j].clone();
+                j -= low_bit!(j);
+            }
+            i -= low_bit!(i);
+        }
+        s
+    }
+}
+
+impl<N, T> From<T> for BinaryIndexedTree2D<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+    T: Into<Vec<Vec<N>>>,
+{
+    /// Constructs a new binary indexed tree from a 1-based grid,
+    /// whose row 0 and column 0 are ignored.
+    ///
+    /// Complexity: _O(nm)_.
+    fn from(a: T) -> Self {
+        let mut body = a.into();
+        let rows = body.len() - 1;
+        let cols = body[0].len() - 1;
+        if body.iter().any(|row| row.len() != cols + 1) {
+            panic!(
+                "Dimension mismatch: every row must have {} elements",
+                cols + 1
+            );
+        }
+        for row in body.iter_mut().skip(1) {
+            row[0] = N::ZERO;
+            for j in 1..=cols {
+                let k = j + low_bit!(j);
+                if k <= cols {
+                    let t = row[j].clone();
+                    row[k] += t;
+                }
+            }
+        }
+        for i in 1..=rows {
+            let k = i + low_bit!(i);
+            if k <= rows {
+                let (lower, upper) = body.split_at_mut(k);
+                for (x, t) in upper[0].iter_mut().zip(&lower[i]).skip(1) {
+                    *x += t.clone();
+                }
+            }
+        }
+        Self { body, rows, cols }
+    }
+}
+
+/// Two-dimensional binary indexed tree for rectangle sum queries and rectangle updates.
+///
+/// It keeps the two-dimensional differences `d` together with `d·i`, `d·j` and `d·i·j`
+/// in four [`BinaryIndexedTree2D`]s.
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::binary_indexed_tree::RangeBinaryIndexedTree2D;
+///
+/// let mut bit = RangeBinaryIndexedTree2D::<i64>::with_size(3, 4);
+/// bit.add(1..=2, 2..=3, 5);
+/// bit.add(2.., .., 1);
+/// assert_eq!(bit.sum(2..=2, 3..), 7);
+/// assert_eq!(bit.sum(.., ..), 28);
+/// ```
+#[derive(Debug)]
+pub struct RangeBinaryIndexedTree2D<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone,
+{
+    d: BinaryIndexedTree2D<N>,
+    di: BinaryIndexedTree2D<N>,
+    dj: BinaryIndexedTree2D<N>,
+    dij: BinaryIndexedTree2D<N>,
+}
+
+impl<N> RangeBinaryIndexedTree2D<N>
+where
+    N: Numeric + NumericOps + NumericAssOps + Clone + TryFrom<usize>,
+    <N as TryFrom<usize>>::Error: Debug,
+{
+    /// Constructs a binary indexed tree of `rows` by `cols` zeros.
+    pub fn with_size(rows: usize, cols: usize) -> Self {
+        Self {
+            d: BinaryIndexedTree2D::with_size(rows, cols),
+            di: BinaryIndexedTree2D::with_size(rows, cols),
+            dj: BinaryIndexedTree2D::with_size(rows, cols),
+            dij: BinaryIndexedTree2D::with_size(rows, cols),
+        }
+    }
+
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        self.d.rows
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        self.d.cols
+    }
+
+    /// Adds `delta` to every element in the rectangle `rows` by `cols`.
+    /// Complexity: _O(log n log m)_.
+    pub fn add(&mut self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>, delta: N) {
+        let (top, bottom) = to_range(rows, self.rows());
+        let (left, right) = to_range(cols, self.cols());
+        if top >= bottom || left >= right {
+            return;
+        }
+        let neg = N::ZERO - delta.clone();
+        self.corner(top, left, delta.clone());
+        self.corner(top, right, neg.clone());
+        self.corner(bottom, left, neg);
+        self.corner(bottom, right, delta);
+    }
+
+    /// Returns the sum of the elements in the rectangle `rows` by `cols`.
+    /// Complexity: _O(log n log m)_.
+    pub fn sum(&self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> N {
+        let (top, bottom) = to_range(rows, self.rows());
+        let (left, right) = to_range(cols, self.cols());
+        if top >= bottom || left >= right {
+            return N::ZERO;
+        }
+        self.prefix(bottom - 1, right - 1)
+            - self.prefix(top - 1, right - 1)
+            - self.prefix(bottom - 1, left - 1)
+            + self.prefix(top - 1, left - 1)
+    }
+
+    /// Adds `delta` to the difference at `(row, col)`, ignoring corners outside the grid.
+    fn corner(&mut self, row: usize, col: usize, delta: N) {
+        if row > self.rows() || col > self.cols() {
+            return;
+        }
+        let (i, j) = (N::try_from(row).unwrap(), N::try_from(col).unwrap());
+        self.d.add(row, col, delta.clone());
+        self.di.add(row, col, delta.clone() * i.clone());
+        self.dj.add(row, col, delta.clone() * j.clone());
+        self.dij.add(row, col, delta * i * j);
+    }
+
+    /// The sum of the rectangle `1..=row` by `1..=col`.
+    fn prefix(&self, row: usize, col: usize) -> N {
+        let (x, y) = (N::try_from(row + 1).unwrap(), N::try_from(col + 1).unwrap());
+        self.d.prefix(row, col) * x.clone() * y.clone()
+            - self.di.prefix(row, col) * y
+            - self.dj.prefix(row, col) * x
+            + self.dij.prefix(row, col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::binary_indexed_tree::{
+        BinaryIndexedTree2D, RangeBinaryIndexedTree2D,
+    };
+    use crate::fscanln;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p4514() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+X 4 4
+L 1 1 3 3 2
+L 2 2 4 4 1
+k 2 2 3 3
+"#,
+        ));
+
+        let (x, n, m): (char, usize, usize);
+        fscanln!(reader, x, n, m);
+        assert_eq!(x, 'X');
+        let mut bit = RangeBinaryIndexedTree2D::<i64>::with_size(n, m);
+        let mut ans = vec![];
+        loop {
+            let (op, a, b, c, d, delta): (char, usize, usize, usize, usize, i64);
+            fscanln!(reader, op, a, b, c, d, delta; ?);
+            match op {
+                'L' => bit.add(a..=c, b..=d, delta),
+                'k' => ans.push(bit.sum(a..=c, b..=d)),
+                _ => break,
+            }
+        }
+        assert_eq!(ans, vec![12]);
+    }
+
+    #[test]
+    fn every_rectangle() {
+        let (n, m) = (5, 6);
+        let grid: Vec<Vec<i64>> = (0..=n)
+            .map(|i| (0..=m).map(|j| (i * j) as i64 % 7 - 3).collect())
+            .collect();
+        let mut point = BinaryIndexedTree2D::from(grid.clone());
+        let mut range = RangeBinaryIndexedTree2D::with_size(n, m);
+        for (i, row) in grid.iter().enumerate().skip(1) {
+            range.add(i..=i, .., row[1]);
+            for j in 2..=m {
+                range.add(i..=i, j.., row[j] - row[j - 1]);
+            }
+        }
+        point.add(4, 2, 10);
+        range.add(4..=4, 2..=2, 10);
+        let mut grid = grid;
+        grid[4][2] += 10;
+        for top in 1..=n {
+            for bottom in top..=n {
+                for left in 1..=m {
+                    for right in left..=m {
+                        let expected: i64 = grid[top..=bottom]
+                            .iter()
+                            .map(|row| row[left..=right].iter().sum::<i64>())
+                            .sum();
+                        assert_eq!(point.sum(top..=bottom, left..=right), expected);
+                        assert_eq!(range.sum(top..=bottom, left..=right), expected);
+                    }
+                }
+            }
+        }
+        assert_eq!(point.sum(3..3, ..), 0);
+        assert_eq!(range.sum(.., 4..4), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds: the size is 2x3 but the index is (3, 1)")]
+    fn out_of_bounds() {
+        BinaryIndexedTree2D::<i64>::with_size(2, 3).add(3, 1, 1);
+    }
+}
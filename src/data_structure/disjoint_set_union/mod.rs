@@ -0,0 +1,242 @@
+pub mod rollback;
+pub mod weighted;
+#[doc(inline)]
+pub use self::rollback::RollbackDisjointSetUnion;
+#[doc(inline)]
+pub use self::weighted::{Conflict, WeightedDisjointSetUnion};
+
+/// Disjoint Set Union, also known as Union-Find Set.
+///
+/// For more information, see [Disjoint Set Union](https://cp-algorithms.com/data_structures/disjoint_set_union.html).
+///
+/// # Examples
+///
+/// ```no_run
+/// use cplit::data_structure::DisjointSetUnion;
+/// use cplit::scanln;
+///
+/// fn main() {
+///     let (n, m): (usize, usize);
+///     scanln!(n, m);
+///     let mut dsu = DisjointSetUnion::with_len(n);
+///     for _ in 0..m {
+///         let (op, x, y): (usize, usize, usize);
+///         scanln!(op, x, y);
+///         match op {
+///             1 => {
+///                 dsu.union(x, y);
+///             }
+///             2 => {
+///                 println!("{}", if dsu.same(x, y) { 'Y' } else { 'N' });
+///             }
+///             _ => unreachable!(),
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DisjointSetUnion {
+    parent: Vec<usize>,
+    /// The size of the component, only meaningful for roots.
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSetUnion {
+    /// Constructs a new disjoint set union with the specified `len`.
+    pub fn with_len(len: usize) -> Self {
+        DisjointSetUnion {
+            parent: (0..=len).collect(),
+            size: vec![1; len + 1],
+            count: len,
+        }
+    }
+
+    /// The length of the disjoint set union.
+    pub fn len(&self) -> usize {
+        self.parent.len() - 1
+    }
+
+    /// Returns `true` if the disjoint set union is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the root of the component containing `x`.
+    /// Complexity: _O(α(n))_ amortized.
+    pub fn find(&mut self, x: usize) -> usize {
+        self.check(x);
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    /// Merges the components containing `x` and `y`.
+    /// Returns `false` if they are already the same component.
+    /// Complexity: _O(α(n))_ amortized.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut fx, mut fy) = (self.find(x), self.find(y));
+        if fx == fy {
+            return false;
+        }
+        if self.size[fx] < self.size[fy] {
+            std::mem::swap(&mut fx, &mut fy);
+        }
+        self.parent[fy] = fx;
+        self.size[fx] += self.size[fy];
+        self.count -= 1;
+        true
+    }
+
+    /// Returns `true` if `x` and `y` are in the same component.
+    /// Complexity: _O(α(n))_ amortized.
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Returns the size of the component containing `x`.
+    /// Complexity: _O(α(n))_ amortized.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// The number of components.
+    pub fn count_components(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the members of every component in ascending order,
+    /// with the components ordered by their smallest member.
+    /// Complexity: _O(n α(n))_.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len() + 1];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.count);
+        for x in 1..=self.len() {
+            let root = self.find(x);
+            if index[root] == usize::MAX {
+                index[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[index[root]].push(x);
+        }
+        groups
+    }
+
+    fn check(&self, x: usize) {
+        if !(1..=self.len()).contains(&x) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len(),
+                x
+            );
+        }
+    }
+}
+
+impl<T> From<T> for DisjointSetUnion
+where
+    T: Into<Vec<usize>>,
+{
+    /// Constructs a new disjoint set union with the specified 1-based `parent`.
+    fn from(a: T) -> Self {
+        let parent = a.into();
+        let len = parent.len() - 1;
+        let mut dsu = DisjointSetUnion {
+            parent,
+            size: vec![0; len + 1],
+            count: 0,
+        };
+        for x in 1..=len {
+            let root = dsu.find(x);
+            if dsu.size[root] == 0 {
+                dsu.count += 1;
+            }
+            dsu.size[root] += 1;
+        }
+        dsu
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::DisjointSetUnion;
+    use crate::fscanln;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p3367() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+4 7
+2 1 2
+1 1 2
+2 1 2
+1 3 4
+2 1 4
+1 2 3
+2 1 4
+"#,
+        ));
+
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let mut dsu = DisjointSetUnion::with_len(n);
+        let mut ans = String::new();
+        for _ in 0..m {
+            let (op, x, y): (usize, usize, usize);
+            fscanln!(reader, op, x, y);
+            match op {
+                1 => {
+                    dsu.union(x, y);
+                }
+                2 => {
+                    ans.push(if dsu.find(x) == dsu.find(y) { 'Y' } else { 'N' });
+                }
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(ans, "NYNY");
+    }
+
+    #[test]
+    fn sizes_and_groups() {
+        let mut dsu = DisjointSetUnion::with_len(8);
+        assert_eq!(dsu.count_components(), 8);
+        assert!(dsu.union(1, 3));
+        assert!(dsu.union(5, 3));
+        assert!(dsu.union(8, 2));
+        assert!(!dsu.union(1, 5));
+        assert!(dsu.union(7, 6));
+        assert_eq!(dsu.count_components(), 4);
+        assert_eq!(dsu.size(3), 3);
+        assert_eq!(dsu.size(4), 1);
+        assert!(dsu.same(2, 8));
+        assert!(!dsu.same(2, 7));
+        assert_eq!(
+            dsu.groups(),
+            vec![vec![1, 3, 5], vec![2, 8], vec![4], vec![6, 7]]
+        );
+    }
+
+    #[test]
+    fn long_chain() {
+        let n = 1_000_000;
+        // 1 -> 2 -> ... -> n, so the first `find` walks the whole chain.
+        let mut parent: Vec<usize> = (0..=n).map(|i| (i + 1).min(n)).collect();
+        parent[0] = 0;
+        let mut dsu = DisjointSetUnion::from(parent);
+        assert_eq!(dsu.count_components(), 1);
+        assert_eq!(dsu.size(1), n);
+        assert!(!dsu.union(1, n));
+        assert_eq!(dsu.find(1), n);
+    }
+}
@@ -0,0 +1,172 @@
+/// Disjoint set union which can undo its unions.
+///
+/// It uses union by size without path compression, so that every union changes only one root
+/// and can be undone in _O(1)_, while `find` takes _O(log n)_.
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::disjoint_set_union::RollbackDisjointSetUnion;
+///
+/// let mut dsu = RollbackDisjointSetUnion::with_len(4);
+/// dsu.union(1, 2);
+/// let snapshot = dsu.snapshot();
+/// dsu.union(2, 3);
+/// dsu.union(4, 1);
+/// assert!(dsu.same(3, 4));
+/// dsu.rollback(snapshot);
+/// assert!(dsu.same(1, 2));
+/// assert!(!dsu.same(1, 3));
+/// assert_eq!(dsu.count_components(), 3);
+/// ```
+#[derive(Debug)]
+pub struct RollbackDisjointSetUnion {
+    parent: Vec<usize>,
+    /// The size of the component, only meaningful for roots.
+    size: Vec<usize>,
+    count: usize,
+    /// The roots attached to another root, in the order of the unions.
+    history: Vec<usize>,
+}
+
+impl RollbackDisjointSetUnion {
+    /// Constructs a new rollback disjoint set union with the specified `len`.
+    pub fn with_len(len: usize) -> Self {
+        RollbackDisjointSetUnion {
+            parent: (0..=len).collect(),
+            size: vec![1; len + 1],
+            count: len,
+            history: vec![],
+        }
+    }
+
+    /// The length of the disjoint set union.
+    pub fn len(&self) -> usize {
+        self.parent.len() - 1
+    }
+
+    /// Returns `true` if the disjoint set union is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the root of the component containing `x`.
+    /// Complexity: _O(log n)_.
+    pub fn find(&self, x: usize) -> usize {
+        if !(1..=self.len()).contains(&x) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len(),
+                x
+            );
+        }
+        let mut x = x;
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the components containing `x` and `y`.
+    /// Returns `false` if they are already the same component.
+    /// Complexity: _O(log n)_.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut fx, mut fy) = (self.find(x), self.find(y));
+        if fx == fy {
+            return false;
+        }
+        if self.size[fx] < self.size[fy] {
+            std::mem::swap(&mut fx, &mut fy);
+        }
+        self.parent[fy] = fx;
+        self.size[fx] += self.size[fy];
+        self.count -= 1;
+        self.history.push(fy);
+        true
+    }
+
+    /// Returns `true` if `x` and `y` are in the same component.
+    /// Complexity: _O(log n)_.
+    pub fn same(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Returns the size of the component containing `x`.
+    /// Complexity: _O(log n)_.
+    pub fn size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    /// The number of components.
+    pub fn count_components(&self) -> usize {
+        self.count
+    }
+
+    /// Returns a snapshot of the current state, to be passed to [`rollback`](Self::rollback).
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes every union since `snapshot` was taken.
+    ///
+    /// Panics if `snapshot` exceeds the current number of unions.
+    /// A snapshot is only a count of unions, so one taken after an earlier snapshot
+    /// must not be used once the state has been rolled back past it.
+    /// Complexity: _O(1)_ per undone union.
+    pub fn rollback(&mut self, snapshot: usize) {
+        if snapshot > self.history.len() {
+            panic!(
+                "Invalid snapshot: there are {} unions but the snapshot is {}",
+                self.history.len(),
+                snapshot
+            );
+        }
+        while self.history.len() > snapshot {
+            let y = self.history.pop().unwrap();
+            let x = self.parent[y];
+            self.parent[y] = y;
+            self.size[x] -= self.size[y];
+            self.count += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::disjoint_set_union::RollbackDisjointSetUnion;
+
+    #[test]
+    fn nested_rollbacks() {
+        let mut dsu = RollbackDisjointSetUnion::with_len(6);
+        let empty = dsu.snapshot();
+        assert!(dsu.union(1, 2));
+        assert!(dsu.union(3, 4));
+        let two = dsu.snapshot();
+        assert!(dsu.union(2, 3));
+        assert!(!dsu.union(1, 4));
+        assert_eq!(dsu.size(4), 4);
+        let three = dsu.snapshot();
+        assert!(dsu.union(5, 6));
+        assert!(dsu.union(6, 1));
+        assert_eq!(dsu.count_components(), 1);
+        dsu.rollback(three);
+        assert_eq!(dsu.count_components(), 3);
+        assert!(!dsu.same(5, 6));
+        assert_eq!(dsu.size(1), 4);
+        dsu.rollback(two);
+        assert!(dsu.same(1, 2) && dsu.same(3, 4) && !dsu.same(2, 3));
+        assert_eq!(dsu.size(3), 2);
+        dsu.rollback(empty);
+        assert_eq!(dsu.count_components(), 6);
+        assert!((1..=6).all(|x| dsu.find(x) == x && dsu.size(x) == 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid snapshot: there are 0 unions but the snapshot is 1")]
+    fn invalid_snapshot() {
+        let mut dsu = RollbackDisjointSetUnion::with_len(2);
+        dsu.union(1, 2);
+        let snapshot = dsu.snapshot();
+        dsu.rollback(0);
+        dsu.rollback(snapshot);
+    }
+}
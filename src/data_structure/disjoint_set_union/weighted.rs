@@ -0,0 +1,256 @@
+use crate::num::Zero;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Neg, Sub};
+
+/// Disjoint set union keeping the difference `a[x] - a[root]` of every element to its root,
+/// for systems of constraints `a[x] - a[y] = w`.
+///
+/// `T` can be any abelian group, such as integers, modular integers,
+/// or bit vectors under XOR.
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::disjoint_set_union::{Conflict, WeightedDisjointSetUnion};
+///
+/// let mut dsu = WeightedDisjointSetUnion::<i64>::with_len(4);
+/// assert_eq!(dsu.union(1, 2, 3), Ok(true));
+/// assert_eq!(dsu.union(3, 2, -1), Ok(true));
+/// assert_eq!(dsu.diff(1, 3), Some(4));
+/// assert_eq!(dsu.union(1, 3, 4), Ok(false));
+/// assert_eq!(dsu.union(3, 1, 4), Err(Conflict));
+/// assert_eq!(dsu.diff(1, 4), None);
+/// ```
+#[derive(Debug)]
+pub struct WeightedDisjointSetUnion<T> {
+    parent: Vec<usize>,
+    /// The size of the component, only meaningful for roots.
+    size: Vec<usize>,
+    /// `a[x] - a[parent[x]]`.
+    weight: Vec<T>,
+}
+
+/// The error returned by [`WeightedDisjointSetUnion::union`]
+/// when a constraint contradicts the previous ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict;
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the constraint contradicts the previous ones")
+    }
+}
+
+impl Error for Conflict {}
+
+impl<T> WeightedDisjointSetUnion<T>
+where
+    T: Clone + PartialEq + Zero + Add<Output = T> + Sub<Output = T> + Neg<Output = T>,
+{
+    /// Constructs a new weighted disjoint set union with the specified `len`.
+    pub fn with_len(len: usize) -> Self {
+        WeightedDisjointSetUnion {
+            parent: (0..=len).collect(),
+            size: vec![1; len + 1],
+            weight: vec![T::ZERO; len + 1],
+        }
+    }
+
+    /// The length of the disjoint set union.
+    pub fn len(&self) -> usize {
+        self.parent.len() - 1
+    }
+
+    /// Returns `true` if the disjoint set union is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the root of the component containing `x`.
+    /// Complexity: _O(α(n))_ amortized.
+    pub fn find(&mut self, x: usize) -> usize {
+        self.check(x);
+        let (mut root, mut total) = (x, T::ZERO);
+        while self.parent[root] != root {
+            total = total + self.weight[root].clone();
+            root = self.parent[root];
+        }
+        // Walk the path again, keeping `total` equal to `a[v] - a[root]`.
+        let mut v = x;
+        while v != root {
+            let p = self.parent[v];
+            let w = std::mem::replace(&mut self.weight[v], total.clone());
+            total = total - w;
+            self.parent[v] = root;
+            v = p;
+        }
+        root
+    }
+
+    /// Returns `true` if `x` and `y` are in the same component.
+    /// Complexity: _O(α(n))_ amortized.
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Returns the size of the component containing `x`.
+    /// Complexity: _O(α(n))_ amortized.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Adds the constraint `a[x] - a[y] = w`.
+    ///
+    /// Returns whether two components are merged,
+    /// or [`Conflict`] if the constraint contradicts the previous ones.
+    /// Complexity: _O(α(n))_ amortized.
+    pub fn union(&mut self, x: usize, y: usize, w: T) -> Result<bool, Conflict> {
+        let (fx, fy) = (self.find(x), self.find(y));
+        // a[fy] - a[fx] = a[x] - w - a[y] - a[fx] + a[fy].
+        let d = self.weight[x].clone() - w - self.weight[y].clone();
+        if fx == fy {
+            return if d == T::ZERO {
+                Ok(false)
+            } else {
+                Err(Conflict)
+            };
+        }
+        if self.size[fx] < self.size[fy] {
+            self.parent[fx] = fy;
+            self.weight[fx] = -d;
+            self.size[fy] += self.size[fx];
+        } else {
+            self.parent[fy] = fx;
+            self.weight[fy] = d;
+            self.size[fx] += self.size[fy];
+        }
+        Ok(true)
+    }
+
+    /// Returns `a[x] - a[y]`, or `None` if it is not determined by the constraints.
+    /// Complexity: _O(α(n))_ amortized.
+    pub fn diff(&mut self, x: usize, y: usize) -> Option<T> {
+        (self.find(x) == self.find(y)).then(|| self.weight[x].clone() - self.weight[y].clone())
+    }
+
+    fn check(&self, x: usize) {
+        if !(1..=self.len()).contains(&x) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len(),
+                x
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::disjoint_set_union::{Conflict, WeightedDisjointSetUnion};
+    use crate::fscanln;
+    use crate::num::{ModInt998244353, Zero};
+    use crate::utils::xorshift::Xorshift;
+    use std::io::{BufReader, Cursor};
+    use std::ops::{Add, Neg, Sub};
+
+    #[test]
+    fn atcoder_abc087_d() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+3 3
+1 2 1
+2 3 1
+1 3 2
+3 3
+1 2 1
+2 3 1
+1 3 5
+"#,
+        ));
+
+        let mut ans = vec![];
+        for _ in 0..2 {
+            let (n, m): (usize, usize);
+            fscanln!(reader, n, m);
+            let mut dsu = WeightedDisjointSetUnion::with_len(n);
+            let mut ok = true;
+            for _ in 0..m {
+                let (l, r, d): (usize, usize, i64);
+                fscanln!(reader, l, r, d);
+                ok &= dsu.union(r, l, d).is_ok();
+            }
+            ans.push(ok);
+        }
+        assert_eq!(ans, vec![true, false]);
+    }
+
+    #[test]
+    fn hidden_values() {
+        // Random trees over the residues modulo 3, labelled with the differences of `a`.
+        type Mint = ModInt998244353;
+        let mut rng = Xorshift::new(0x853c49e6748fea9b);
+        let n = 60;
+        let a: Vec<Mint> = (0..=n).map(|_| Mint::from(rng.next_u64())).collect();
+        let mut dsu = WeightedDisjointSetUnion::with_len(n);
+        for x in 4..=n {
+            let y = x - 3 * (rng.next((x - 1) / 3) + 1);
+            assert_eq!(dsu.union(x, y, a[x] - a[y]), Ok(true));
+        }
+        for x in 1..=n {
+            for y in 1..=n {
+                if x % 3 == y % 3 {
+                    assert_eq!(dsu.diff(x, y), Some(a[x] - a[y]));
+                    assert_eq!(dsu.union(x, y, a[x] - a[y]), Ok(false));
+                    assert_eq!(dsu.union(x, y, a[x] - a[y] + Mint::from(1)), Err(Conflict));
+                } else {
+                    assert_eq!(dsu.diff(x, y), None);
+                }
+            }
+        }
+        assert_eq!(dsu.size(1), n / 3);
+    }
+
+    #[test]
+    fn xor() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Xor(u32);
+
+        impl Zero for Xor {
+            const ZERO: Self = Xor(0);
+        }
+
+        impl Add for Xor {
+            type Output = Self;
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn add(self, rhs: Self) -> Self {
+                Xor(self.0 ^ rhs.0)
+            }
+        }
+
+        impl Sub for Xor {
+            type Output = Self;
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn sub(self, rhs: Self) -> Self {
+                Xor(self.0 ^ rhs.0)
+            }
+        }
+
+        impl Neg for Xor {
+            type Output = Self;
+            fn neg(self) -> Self {
+                self
+            }
+        }
+
+        let mut dsu = WeightedDisjointSetUnion::with_len(5);
+        assert_eq!(dsu.union(1, 2, Xor(0b101)), Ok(true));
+        assert_eq!(dsu.union(3, 4, Xor(0b011)), Ok(true));
+        assert_eq!(dsu.union(2, 3, Xor(0b110)), Ok(true));
+        assert_eq!(dsu.diff(1, 4), Some(Xor(0b000)));
+        assert_eq!(dsu.diff(4, 2), Some(Xor(0b101)));
+        assert_eq!(dsu.union(4, 1, Xor(1)), Err(Conflict));
+        assert_eq!(dsu.diff(5, 1), None);
+        assert_eq!(dsu.size(3), 4);
+    }
+}
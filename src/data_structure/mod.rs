@@ -4,15 +4,46 @@
 //!
 //! For now, the following data structures are available:
 //!
-//! [`DisjointSetUnion`], [`SegmentTree`], [`BinaryIndexedTree`]
+//! [`DisjointSetUnion`], [`WeightedDisjointSetUnion`], [`RollbackDisjointSetUnion`],
+//! [`SegmentTree`], [`BottomUpSegmentTree`], [`SegmentTreeBeats`], [`BinaryIndexedTree`]
 
 pub mod binary_indexed_tree;
 pub mod disjoint_set_union;
 pub mod segment_tree;
+pub mod segment_tree_beats;
 
 #[doc(inline)]
 pub use self::binary_indexed_tree::BinaryIndexedTree;
 #[doc(inline)]
-pub use self::disjoint_set_union::DisjointSetUnion;
+pub use self::disjoint_set_union::{
+    DisjointSetUnion, RollbackDisjointSetUnion, WeightedDisjointSetUnion,
+};
 #[doc(inline)]
-pub use self::segment_tree::SegmentTree;
+pub use self::segment_tree::{BottomUpSegmentTree, SegmentTree};
+#[doc(inline)]
+pub use self::segment_tree_beats::SegmentTreeBeats;
+
+use std::ops::{Bound, RangeBounds};
+
+/// Converts `bounds` within `1..=len` into a half-open range `(start, end)`.
+///
+/// Panics if `bounds` is out of `1..=len`.
+pub(crate) fn to_range(bounds: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match bounds.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s + 1,
+        Bound::Unbounded => 1,
+    };
+    let end = match bounds.end_bound() {
+        Bound::Included(&e) => e + 1,
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len + 1,
+    };
+    if !(1..=len + 1).contains(&start) || !(1..=len + 1).contains(&end) {
+        panic!(
+            "Query out of bounds: the range is 1..={} but the query is {}..{}",
+            len, start, end,
+        );
+    }
+    (start, end)
+}
@@ -0,0 +1,192 @@
+use crate::data_structure::segment_tree::Monoid;
+use crate::data_structure::to_range;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+
+/// Non-recursive segment tree for range queries and point updates over a [`Monoid`].
+///
+/// It stores `2n` values and no tags, and walks the tree bottom-up without recursion,
+/// so it is several times faster than [`SegmentTree`](crate::data_structure::SegmentTree)
+/// when no range update is needed.
+/// `COMBINE` need not be commutative.
+///
+/// For more information, see [Efficient and easy segment trees](https://codeforces.com/blog/entry/18051).
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::{BottomUpSegmentTree, segment_tree::AddSum};
+///
+/// let mut st = BottomUpSegmentTree::<i64, AddSum>::from(vec![0, 1, 5, 4, 2, 3]);
+/// assert_eq!(st.query(2..=4), 11);
+/// st.set(3, 10);
+/// assert_eq!(st.get(3), 10);
+/// assert_eq!(st.query(..), 21);
+/// ```
+#[derive(Debug)]
+pub struct BottomUpSegmentTree<V, M>
+where
+    V: Clone,
+    M: Monoid<V>,
+{
+    /// The leaf of index `i` is at `len + i - 1`, and node `x` combines `2x` and `2x + 1`.
+    val: Vec<V>,
+    len: usize,
+    phantom: PhantomData<M>,
+}
+
+impl<V, M> BottomUpSegmentTree<V, M>
+where
+    V: Clone,
+    M: Monoid<V>,
+{
+    /// The length of the segment tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the segment tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Sets the element at `index` to `val`.
+    /// Complexity: _O(log n)_.
+    pub fn set(&mut self, index: usize, val: V) {
+        self.check(index);
+        let mut x = self.len + index - 1;
+        self.val[x] = val;
+        while x > 1 {
+            x >>= 1;
+            self.val[x] = M::COMBINE(self.val[x << 1].clone(), self.val[x << 1 | 1].clone());
+        }
+    }
+
+    /// Returns the element at `index`.
+    /// Complexity: _O(1)_.
+    pub fn get(&self, index: usize) -> V {
+        self.check(index);
+        self.val[self.len + index - 1].clone()
+    }
+
+    /// Returns the combined value of the elements in the range `bounds`,
+    /// or `M::IDENTITY` if it is empty.
+    /// Complexity: _O(log n)_.
+    pub fn query(&self, bounds: impl RangeBounds<usize>) -> V {
+        let (start, end) = to_range(bounds, self.len);
+        let (mut l, mut r) = (self.len + start - 1, self.len + end - 1);
+        let (mut left, mut right) = (M::IDENTITY, M::IDENTITY);
+        while l < r {
+            if l & 1 == 1 {
+                left = M::COMBINE(left, self.val[l].clone());
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right = M::COMBINE(self.val[r].clone(), right);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        M::COMBINE(left, right)
+    }
+
+    fn check(&self, index: usize) {
+        if !(1..=self.len).contains(&index) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len, index
+            );
+        }
+    }
+}
+
+impl<V, M, Q> From<Q> for BottomUpSegmentTree<V, M>
+where
+    V: Clone,
+    M: Monoid<V>,
+    Q: Into<Vec<V>>,
+{
+    /// Constructs a new segment tree from a 1-based collection.
+    ///
+    /// Complexity: _O(n)_.
+    fn from(a: Q) -> Self {
+        let v = a.into();
+        let len = v.len() - 1;
+        let mut val = vec![M::IDENTITY; len];
+        val.extend(v.into_iter().skip(1));
+        for x in (1..len).rev() {
+            val[x] = M::COMBINE(val[x << 1].clone(), val[x << 1 | 1].clone());
+        }
+        Self {
+            val,
+            len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::segment_tree::{AddSum, Monoid};
+    use crate::data_structure::BottomUpSegmentTree;
+    use crate::fscanln;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p3374() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+5 5
+1 5 4 2 3
+1 1 3
+2 2 5
+1 3 -1
+1 4 2
+2 1 4
+"#,
+        ));
+
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let v: Vec<isize>;
+        fscanln!(reader, v; n);
+        let mut st = BottomUpSegmentTree::<isize, AddSum>::from(v);
+        let mut ans = vec![];
+        for _ in 0..m {
+            let (op, x, y): (usize, usize, isize);
+            fscanln!(reader, op, x, y);
+            match op {
+                1 => st.set(x, st.get(x) + y),
+                2 => ans.push(st.query(x..=y as usize)),
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(ans, vec![14, 16]);
+    }
+
+    #[test]
+    fn non_commutative() {
+        /// Concatenation of decimal digits, as `(value, 10^len)` modulo a prime.
+        struct Concat;
+
+        impl Monoid<(u64, u64)> for Concat {
+            const COMBINE: fn((u64, u64), (u64, u64)) -> (u64, u64) =
+                |(a, p), (b, q)| ((a * q + b) % 998244353, p * q % 998244353);
+            const IDENTITY: (u64, u64) = (0, 1);
+        }
+
+        let digits = [0, 3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let n = digits.len() - 1;
+        let mut st = BottomUpSegmentTree::<_, Concat>::from(digits.map(|d| (d, 10)).to_vec());
+        for l in 1..=n {
+            for r in l..=n {
+                let s: String = digits[l..=r].iter().map(|d| d.to_string()).collect();
+                assert_eq!(st.query(l..=r).0, s.parse::<u64>().unwrap() % 998244353);
+            }
+        }
+        st.set(1, (7, 10));
+        assert_eq!(st.query(..3).0, 71);
+        assert_eq!(st.query(3..3), (0, 1));
+    }
+}
@@ -0,0 +1,355 @@
+use crate::data_structure::segment_tree::Operation;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+/// Segment tree over a huge index range, creating its nodes on demand.
+///
+/// Every element starts as the same value `init`, and a node is only allocated when
+/// an update or a query reaches inside it, so the memory is _O(q log n)_ after `q` operations
+/// regardless of the length `n` of the index range.
+/// Indices can be of any primitive integer type, including negative ones and `usize`,
+/// as long as their values fit in `i128`.
+///
+/// It uses the same [`Operation`] as [`SegmentTree`](crate::data_structure::SegmentTree).
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::segment_tree::{AddMin, AddSum, DynamicSegmentTree};
+///
+/// let mut st = DynamicSegmentTree::<i64, i64, AddSum, u64>::new(1..=1_000_000_000_000_000_000, 0);
+/// st.update(1..=500_000_000_000_000_000, 2);
+/// st.update(400_000_000_000_000_001.., 1);
+/// assert_eq!(st.query(400_000_000_000_000_001..=400_000_000_000_000_010), 30);
+/// assert_eq!(st.get(999_999_999_999_999_999), 1);
+///
+/// let mut st = DynamicSegmentTree::<i64, i64, AddMin>::new(-1_000_000_000..=1_000_000_000, 0);
+/// st.update(-5..=5, -3);
+/// assert_eq!(st.query(..), -3);
+/// assert_eq!(st.query(6..), 0);
+/// ```
+#[derive(Debug)]
+pub struct DynamicSegmentTree<V, T, O, I = i64>
+where
+    V: Clone + Copy,
+    T: Clone + Copy,
+    O: Operation<V, T>,
+{
+    /// Node 0 is a buffer, node 1 is the root.
+    nodes: Vec<Node<V, T>>,
+    start: i128,
+    end: i128,
+    init: V,
+    /// The value of an untouched node by its length.
+    fill: HashMap<usize, V>,
+    phantom: PhantomData<(O, I)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node<V, T> {
+    val: V,
+    tag: T,
+    /// The children, `0` if not created yet.
+    left: usize,
+    right: usize,
+}
+
+impl<V, T, O, I> DynamicSegmentTree<V, T, O, I>
+where
+    V: Clone + Copy,
+    T: Clone + Copy + PartialEq,
+    O: Operation<V, T>,
+    I: Copy + TryInto<i128>,
+{
+    /// Constructs a new segment tree over `range` with every element set to `init`.
+    pub fn new(range: RangeInclusive<I>, init: V) -> Self {
+        let (start, end) = (widen(*range.start()), widen(*range.end()));
+        if start > end || end - start >= usize::MAX as i128 {
+            panic!("The length of a segment tree must be in 1..=usize::MAX");
+        }
+        let mut st = Self {
+            nodes: vec![],
+            start,
+            end,
+            init,
+            fill: HashMap::new(),
+            phantom: PhantomData,
+        };
+        let root = st.node(start, end);
+        st.nodes.push(root);
+        st.nodes.push(root);
+        st
+    }
+
+    /// The number of allocated nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    /// Sets the element at `index` to `val`.
+    /// Complexity: _O(log n)_.
+    pub fn set(&mut self, index: I, val: V) {
+        let index = self.check(index);
+        self.assign(1, self.start, self.end, index, val);
+    }
+
+    /// Returns the element at `index`.
+    /// Complexity: _O(log n)_.
+    pub fn get(&mut self, index: I) -> V {
+        let index = self.check(index);
+        self.fold(1, self.start, self.end, index, index)
+    }
+
+    /// Applies `tag` to the elements in the range `bounds`.
+    /// Complexity: _O(log n)_.
+    pub fn update(&mut self, bounds: impl RangeBounds<I>, tag: T) {
+        if let Some((ql, qr)) = self.to_range(bounds) {
+            self.modify(1, self.start, self.end, ql, qr, tag);
+        }
+    }
+
+    /// Returns the combined value of the elements in the range `bounds`,
+    /// or `O::VAL_IDENTITY` if it is empty.
+    /// Complexity: _O(log n)_.
+    pub fn query(&mut self, bounds: impl RangeBounds<I>) -> V {
+        match self.to_range(bounds) {
+            Some((ql, qr)) => self.fold(1, self.start, self.end, ql, qr),
+            None => O::VAL_IDENTITY,
+        }
+    }
+
+    fn check(&self, index: I) -> i128 {
+        let index = widen(index);
+        if !(self.start..=self.end).contains(&index) {
+            panic!(
+                "Index out of bounds: the range is {}..={} but the index is {}",
+                self.start, self.end, index
+            );
+        }
+        index
+    }
+
+    /// Converts `bounds` into a closed range, `None` if it is empty.
+    fn to_range(&self, bounds: impl RangeBounds<I>) -> Option<(i128, i128)> {
+        let start = match bounds.start_bound() {
+            Bound::Included(&s) => widen(s),
+            Bound::Excluded(&s) => widen(s) + 1,
+            Bound::Unbounded => self.start,
+        };
+        let end = match bounds.end_bound() {
+            Bound::Included(&e) => widen(e) + 1,
+            Bound::Excluded(&e) => widen(e),
+            Bound::Unbounded => self.end + 1,
+        };
+        let range = self.start..=self.end + 1;
+        if !range.contains(&start) || !range.contains(&end) {
+            panic!(
+                "Query out of bounds: the range is {}..={} but the query is {}..{}",
+                self.start, self.end, start, end
+            );
+        }
+        (start < end).then_some((start, end - 1))
+    }
+
+    /// The combined value of `len` copies of `init`.
+    fn repeat(&mut self, len: usize) -> V {
+        if len == 1 {
+            return self.init;
+        }
+        if let Some(&val) = self.fill.get(&len) {
+            return val;
+        }
+        let val = O::COMBINE(self.repeat(len - len / 2), self.repeat(len / 2));
+        self.fill.insert(len, val);
+        val
+    }
+
+    /// An untouched node over `l..=r`.
+    fn node(&mut self, l: i128, r: i128) -> Node<V, T> {
+        Node {
+            val: self.repeat((r - l + 1) as usize),
+            tag: O::TAG_IDENTITY,
+            left: 0,
+            right: 0,
+        }
+    }
+
+    fn pushup(&mut self, x: usize) {
+        let Node { left, right, .. } = self.nodes[x];
+        self.nodes[x].val = O::COMBINE(self.nodes[left].val, self.nodes[right].val);
+    }
+
+    fn pushdown(&mut self, x: usize, l: i128, r: i128) {
+        let m = (l + r) >> 1;
+        if self.nodes[x].left == 0 {
+            let (left, right) = (self.node(l, m), self.node(m + 1, r));
+            self.nodes[x].left = self.nodes.len();
+            self.nodes.push(left);
+            self.nodes[x].right = self.nodes.len();
+            self.nodes.push(right);
+        }
+        let Node {
+            tag, left, right, ..
+        } = self.nodes[x];
+        if tag == O::TAG_IDENTITY {
+            return;
+        }
+        for (child, len) in [(left, m - l + 1), (right, r - m)] {
+            let node = &mut self.nodes[child];
+            node.val = O::PUSH_VAL(node.val, tag, len as usize);
+            node.tag = O::PUSH_TAG(node.tag, tag);
+        }
+        self.nodes[x].tag = O::TAG_IDENTITY;
+    }
+
+    fn assign(&mut self, x: usize, l: i128, r: i128, index: i128, val: V) {
+        if l == r {
+            self.nodes[x].val = val;
+        } else {
+            self.pushdown(x, l, r);
+            let m = (l + r) >> 1;
+            if index <= m {
+                self.assign(self.nodes[x].left, l, m, index, val);
+            } else {
+                self.assign(self.nodes[x].right, m + 1, r, index, val);
+            }
+            self.pushup(x);
+        }
+    }
+
+    fn modify(&mut self, x: usize, l: i128, r: i128, ql: i128, qr: i128, delta: T) {
+        if ql <= l && r <= qr {
+            let node = &mut self.nodes[x];
+            node.val = O::PUSH_VAL(node.val, delta, (r - l + 1) as usize);
+            node.tag = O::PUSH_TAG(node.tag, delta);
+        } else {
+            self.pushdown(x, l, r);
+            let m = (l + r) >> 1;
+            if ql <= m {
+                self.modify(self.nodes[x].left, l, m, ql, qr, delta);
+            }
+            if m < qr {
+                self.modify(self.nodes[x].right, m + 1, r, ql, qr, delta);
+            }
+            self.pushup(x);
+        }
+    }
+
+    fn fold(&mut self, x: usize, l: i128, r: i128, ql: i128, qr: i128) -> V {
+        if ql <= l && r <= qr {
+            return self.nodes[x].val;
+        }
+        if self.nodes[x].left == 0 && self.nodes[x].tag == O::TAG_IDENTITY {
+            // An untouched node holds `init` everywhere.
+            let len = (qr.min(r) - ql.max(l) + 1) as usize;
+            return self.repeat(len);
+        }
+        self.pushdown(x, l, r);
+        let m = (l + r) >> 1;
+        match (ql <= m, m < qr) {
+            (true, true) => O::COMBINE(
+                self.fold(self.nodes[x].left, l, m, ql, qr),
+                self.fold(self.nodes[x].right, m + 1, r, ql, qr),
+            ),
+            (true, false) => self.fold(self.nodes[x].left, l, m, ql, qr),
+            (false, true) => self.fold(self.nodes[x].right, m + 1, r, ql, qr),
+            (false, false) => unreachable!(),
+        }
+    }
+}
+
+/// Converts an index into `i128`.
+fn widen<I: TryInto<i128>>(index: I) -> i128 {
+    index
+        .try_into()
+        .unwrap_or_else(|_| panic!("Index out of bounds: the index does not fit in i128"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::segment_tree::{
+        AddMax, AddSum, AffineSum, AssignSum, DynamicSegmentTree,
+    };
+    use crate::data_structure::SegmentTree;
+    use crate::num::ModInt998244353;
+
+    /// Compares every range of the dynamic tree over `-30..=30` with the plain one over `1..=61`.
+    fn check(
+        dynamic: &mut DynamicSegmentTree<i64, i64, AddMax>,
+        plain: &mut SegmentTree<i64, i64, AddMax>,
+    ) {
+        for l in -30..=30 {
+            for r in l..=30 {
+                assert_eq!(
+                    dynamic.query(l..=r),
+                    plain.query((l + 31) as usize..=(r + 31) as usize)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_range() {
+        let mut dynamic = DynamicSegmentTree::<i64, i64, AddMax>::new(-30..=30, 7);
+        let mut plain = SegmentTree::<i64, i64, AddMax>::from(vec![7; 62]);
+        check(&mut dynamic, &mut plain);
+        for (l, r, x) in [
+            (-12, 5, -4),
+            (0, 0, 9),
+            (3, 27, 5),
+            (-30, 30, 2),
+            (-30, -29, -1),
+        ] {
+            dynamic.update(l..=r, x);
+            plain.update((l + 31) as usize..=(r + 31) as usize, x);
+            check(&mut dynamic, &mut plain);
+        }
+        dynamic.set(-7, 40);
+        plain.set(24, 40);
+        check(&mut dynamic, &mut plain);
+        assert_eq!(dynamic.get(-7), 40);
+        assert_eq!(dynamic.query(..), 40);
+    }
+
+    #[test]
+    fn huge_range() {
+        type Mint = ModInt998244353;
+        let n = 1_000_000_000_000_000_000_u64;
+        let mut st =
+            DynamicSegmentTree::<Mint, (Mint, Mint), AffineSum, u64>::new(1..=n, Mint::from(1));
+        assert_eq!(st.query(..), Mint::from(n));
+        st.update(..=n / 2, (Mint::from(3), Mint::from(1)));
+        st.update(n / 4.., (Mint::from(2), Mint::from(0)));
+        // [1, n/4): 4, [n/4, n/2]: 8, (n/2, n]: 2.
+        let expected = Mint::from(4) * Mint::from(n / 4 - 1)
+            + Mint::from(8) * Mint::from(n / 2 - n / 4 + 1)
+            + Mint::from(2) * Mint::from(n - n / 2);
+        assert_eq!(st.query(..), expected);
+        assert_eq!(st.get(n / 4 - 1), Mint::from(4));
+        assert_eq!(st.get(n / 4), Mint::from(8));
+        assert!(st.node_count() < 1000);
+
+        let mut st = DynamicSegmentTree::<i64, i64, AddSum, u64>::new(1..=u64::MAX, 0);
+        st.set(u64::MAX, 5);
+        st.update(u64::MAX - 9.., 1);
+        assert_eq!(st.query(u64::MAX - 20..), 15);
+    }
+
+    #[test]
+    fn narrow_values() {
+        // The lengths of the nodes do not fit in `i32`, but the sums do.
+        let n = 1_000_000_000_000_usize;
+        let mut st = DynamicSegmentTree::<i32, Option<i32>, AssignSum, usize>::new(0..=n, 0);
+        st.update(.., Some(0));
+        st.set(n, 5);
+        st.update(n / 2..=n / 2 + 2, Some(-3));
+        assert_eq!(st.query(..), -4);
+        assert_eq!(st.query(..n), -9);
+
+        let start = 1_u128 << 100;
+        let mut st = DynamicSegmentTree::<i64, i64, AddSum, u128>::new(start..=start + 1000, 0);
+        st.update(start + 10..start + 14, 2);
+        assert_eq!(st.query(..), 8);
+    }
+}
@@ -1,16 +1,28 @@
+pub mod bottom_up;
+pub mod dynamic;
 pub mod ops;
+pub mod persistent;
 #[doc(inline)]
-pub use self::ops::{AddSum, Operation, OperationPair};
+pub use self::bottom_up::BottomUpSegmentTree;
+#[doc(inline)]
+pub use self::dynamic::DynamicSegmentTree;
+#[doc(inline)]
+pub use self::ops::{
+    AddMax, AddMin, AddSum, AffineSum, AssignMax, AssignMin, AssignSum, ChmaxMin, ChminMax, Gcd,
+    Monoid, Operation, OperationPair,
+};
+#[doc(inline)]
+pub use self::persistent::{KthSmallest, PersistentSegmentTree};
 
+use crate::data_structure::to_range;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::ops::RangeBounds;
 
-/// Segment tree for range queries and point updates.
+/// Segment tree for range queries and range updates with lazy tags.
 ///
 /// For more information, see [Segment tree](https://cp-algorithms.com/data_structures/segment_tree.html).
 ///
-/// The interface of the segment tree is a bit redundant for now, may fix it later.
-///
 /// # Example 1
 /// ```no_run
 /// use cplit::data_structure::{SegmentTree, segment_tree::AddSum};
@@ -27,8 +39,8 @@ use std::marker::PhantomData;
 ///         let (op, x, y, k): (usize, usize, usize, isize);
 ///         scanln!(op, x, y, k; ?);
 ///         match op {
-///             1 => st.modify(1, 1, n, x, y, k),
-///             2 => println!("{}", st.query(1, 1, n, x, y)),
+///             1 => st.update(x..=y, k),
+///             2 => println!("{}", st.query(x..=y)),
 ///             _ => unreachable!(),
 ///         }
 ///     }
@@ -73,9 +85,9 @@ use std::marker::PhantomData;
 ///         let (op, x, y, k): (usize, usize, usize, isize);
 ///         scanln!(op, x, y, k; ?);
 ///         match op {
-///             1 => st.modify(1, 1, n, x, y, (0, k)), // mul
-///             2 => st.modify(1, 1, n, x, y, (k, 1)), // add
-///             3 => println!("{}", st.query(1, 1, n, x, y)),
+///             1 => st.update(x..=y, (0, k)), // mul
+///             2 => st.update(x..=y, (k, 1)), // add
+///             3 => println!("{}", st.query(x..=y)),
 ///             _ => unreachable!(),
 ///         }
 ///     }
@@ -84,7 +96,7 @@ use std::marker::PhantomData;
 #[derive(Debug)]
 pub struct SegmentTree<V, T, O>
 where
-    V: Clone + Copy,
+    V: Clone,
     T: Clone + Copy,
     O: Operation<V, T>,
 {
@@ -96,34 +108,140 @@ where
 
 impl<V, T, O> SegmentTree<V, T, O>
 where
-    V: Clone + Copy,
+    V: Clone,
     T: Clone + Copy + PartialEq,
     O: Operation<V, T>,
 {
+    /// The length of the segment tree.
     pub fn len(&self) -> usize {
         self.len
     }
 
+    /// Returns `true` if the segment tree is empty.
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
 
+    /// Sets the element at `index` to `val`.
+    /// Complexity: _O(log n)_.
+    pub fn set(&mut self, index: usize, val: V) {
+        self.check(index);
+        self.assign(1, 1, self.len, index, val);
+    }
+
+    /// Returns the element at `index`.
+    /// Complexity: _O(log n)_.
+    pub fn get(&mut self, index: usize) -> V {
+        self.check(index);
+        self.fold(1, 1, self.len, index, index)
+    }
+
+    /// Applies `tag` to the elements in the range `bounds`.
+    /// Complexity: _O(log n)_.
+    pub fn update(&mut self, bounds: impl RangeBounds<usize>, tag: T) {
+        let (start, end) = to_range(bounds, self.len);
+        if start < end {
+            self.modify(1, 1, self.len, start, end - 1, tag);
+        }
+    }
+
+    /// Returns the combined value of the elements in the range `bounds`,
+    /// or `O::VAL_IDENTITY` if it is empty.
+    /// Complexity: _O(log n)_.
+    pub fn query(&mut self, bounds: impl RangeBounds<usize>) -> V {
+        let (start, end) = to_range(bounds, self.len);
+        if start < end {
+            self.fold(1, 1, self.len, start, end - 1)
+        } else {
+            O::VAL_IDENTITY
+        }
+    }
+
+    /// Returns the largest `r` in `start - 1..=n` such that `pred(query(start..=r))` holds,
+    /// given that `pred` holds for `O::VAL_IDENTITY`.
+    ///
+    /// If `pred` is monotone, it is where `pred` turns from `true` to `false`,
+    /// like the first prefix sum exceeding `k` is at `max_right(1, |s| s <= k) + 1`.
+    /// Complexity: _O(log n)_.
+    pub fn max_right(&mut self, start: usize, pred: impl Fn(V) -> bool) -> usize {
+        if !(1..=self.len + 1).contains(&start) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len + 1,
+                start
+            );
+        }
+        if start > self.len {
+            return self.len;
+        }
+        let mut acc = O::VAL_IDENTITY;
+        self.descend_right(1, 1, self.len, start, &mut acc, &pred)
+            .map_or(self.len, |p| p - 1)
+    }
+
+    /// Returns the smallest `l` in `1..=end + 1` such that `pred(query(l..=end))` holds,
+    /// given that `pred` holds for `O::VAL_IDENTITY`.
+    /// Complexity: _O(log n)_.
+    pub fn min_left(&mut self, end: usize, pred: impl Fn(V) -> bool) -> usize {
+        if end > self.len {
+            panic!(
+                "Index out of bounds: the range is 0..={} but the index is {}",
+                self.len, end
+            );
+        }
+        if end == 0 {
+            return 1;
+        }
+        let mut acc = O::VAL_IDENTITY;
+        self.descend_left(1, 1, self.len, end, &mut acc, &pred)
+            .map_or(1, |p| p + 1)
+    }
+
+    fn check(&self, index: usize) {
+        if !(1..=self.len).contains(&index) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len, index
+            );
+        }
+    }
+
+    /// Moves the value of node `x` out, leaving the identity behind.
+    fn take(&mut self, x: usize) -> V {
+        std::mem::replace(&mut self.val[x], O::VAL_IDENTITY)
+    }
+
     fn pushup(&mut self, x: usize) {
-        self.val[x] = O::COMBINE(self.val[x << 1], self.val[x << 1 | 1]);
+        self.val[x] = O::COMBINE(self.val[x << 1].clone(), self.val[x << 1 | 1].clone());
     }
 
     fn pushdown(&mut self, x: usize, l: usize, r: usize) {
         let m = (l + r) >> 1;
-        self.val[x << 1] = O::PUSH_VAL(self.val[x << 1], self.tag[x], m - l + 1);
-        self.val[x << 1 | 1] = O::PUSH_VAL(self.val[x << 1 | 1], self.tag[x], r - m);
+        self.val[x << 1] = O::PUSH_VAL(self.take(x << 1), self.tag[x], m - l + 1);
+        self.val[x << 1 | 1] = O::PUSH_VAL(self.take(x << 1 | 1), self.tag[x], r - m);
         self.tag[x << 1] = O::PUSH_TAG(self.tag[x << 1], self.tag[x]);
         self.tag[x << 1 | 1] = O::PUSH_TAG(self.tag[x << 1 | 1], self.tag[x]);
         self.tag[x] = O::TAG_IDENTITY;
     }
 
-    pub fn modify(&mut self, x: usize, l: usize, r: usize, ql: usize, qr: usize, delta: T) {
+    fn assign(&mut self, x: usize, l: usize, r: usize, index: usize, val: V) {
+        if l == r {
+            self.val[x] = val;
+        } else {
+            self.pushdown(x, l, r);
+            let m = (l + r) >> 1;
+            if index <= m {
+                self.assign(x << 1, l, m, index, val);
+            } else {
+                self.assign(x << 1 | 1, m + 1, r, index, val);
+            }
+            self.pushup(x);
+        }
+    }
+
+    fn modify(&mut self, x: usize, l: usize, r: usize, ql: usize, qr: usize, delta: T) {
         if ql <= l && r <= qr {
-            self.val[x] = O::PUSH_VAL(self.val[x], delta, r - l + 1);
+            self.val[x] = O::PUSH_VAL(self.take(x), delta, r - l + 1);
             self.tag[x] = O::PUSH_TAG(self.tag[x], delta);
         } else {
             self.pushdown(x, l, r);
@@ -138,27 +256,85 @@ where
         }
     }
 
-    pub fn query(&mut self, x: usize, l: usize, r: usize, ql: usize, qr: usize) -> V {
+    fn fold(&mut self, x: usize, l: usize, r: usize, ql: usize, qr: usize) -> V {
         if ql <= l && r <= qr {
-            self.val[x]
+            self.val[x].clone()
         } else {
             self.pushdown(x, l, r);
             let m = (l + r) >> 1;
             match (ql <= m, m < qr) {
                 (true, true) => O::COMBINE(
-                    self.query(x << 1, l, m, ql, qr),
-                    self.query(x << 1 | 1, m + 1, r, ql, qr),
+                    self.fold(x << 1, l, m, ql, qr),
+                    self.fold(x << 1 | 1, m + 1, r, ql, qr),
                 ),
-                (true, false) => self.query(x << 1, l, m, ql, qr),
-                (false, true) => self.query(x << 1 | 1, m + 1, r, ql, qr),
+                (true, false) => self.fold(x << 1, l, m, ql, qr),
+                (false, true) => self.fold(x << 1 | 1, m + 1, r, ql, qr),
                 (false, false) => unreachable!(),
             }
         }
     }
 
+    /// The first index from `ql` where `pred` fails on the folded prefix `acc`, if any.
+    fn descend_right(
+        &mut self,
+        x: usize,
+        l: usize,
+        r: usize,
+        ql: usize,
+        acc: &mut V,
+        pred: &impl Fn(V) -> bool,
+    ) -> Option<usize> {
+        if r < ql {
+            return None;
+        }
+        if ql <= l {
+            let val = O::COMBINE(acc.clone(), self.val[x].clone());
+            if pred(val.clone()) {
+                *acc = val;
+                return None;
+            }
+            if l == r {
+                return Some(l);
+            }
+        }
+        self.pushdown(x, l, r);
+        let m = (l + r) >> 1;
+        self.descend_right(x << 1, l, m, ql, acc, pred)
+            .or_else(|| self.descend_right(x << 1 | 1, m + 1, r, ql, acc, pred))
+    }
+
+    /// The last index up to `qr` where `pred` fails on the folded suffix `acc`, if any.
+    fn descend_left(
+        &mut self,
+        x: usize,
+        l: usize,
+        r: usize,
+        qr: usize,
+        acc: &mut V,
+        pred: &impl Fn(V) -> bool,
+    ) -> Option<usize> {
+        if qr < l {
+            return None;
+        }
+        if r <= qr {
+            let val = O::COMBINE(self.val[x].clone(), acc.clone());
+            if pred(val.clone()) {
+                *acc = val;
+                return None;
+            }
+            if l == r {
+                return Some(l);
+            }
+        }
+        self.pushdown(x, l, r);
+        let m = (l + r) >> 1;
+        self.descend_left(x << 1 | 1, m + 1, r, qr, acc, pred)
+            .or_else(|| self.descend_left(x << 1, l, m, qr, acc, pred))
+    }
+
     fn init(&mut self, x: usize, l: usize, r: usize, a: &Vec<V>) {
         if l == r {
-            self.val[x] = a[l]
+            self.val[x] = a[l].clone()
         } else {
             let m = (l + r) >> 1;
             self.init(x << 1, l, m, a);
@@ -170,12 +346,12 @@ where
 
 impl<V, T, O, Q> From<Q> for SegmentTree<V, T, O>
 where
-    V: Clone + Copy,
+    V: Clone,
     T: Clone + Copy + PartialEq,
     O: Operation<V, T>,
     Q: Into<Vec<V>>,
 {
-    /// Constructs a new binary indexed tree.
+    /// Constructs a new segment tree from a 1-based collection.
     ///
     /// Complexity: _O(n)_.
     fn from(a: Q) -> Self {
@@ -187,7 +363,9 @@ where
             len,
             phantom: PhantomData,
         };
-        st.init(1, 1, len, &v);
+        if len > 0 {
+            st.init(1, 1, len, &v);
+        }
         st
     }
 }
@@ -223,8 +401,8 @@ mod tests {
             let (op, x, y, k): (usize, usize, usize, isize);
             fscanln!(reader, op, x, y, k; ?);
             match op {
-                1 => st.modify(1, 1, n, x, y, k),
-                2 => ans.push_str(&format!("{}\n", st.query(1, 1, n, x, y))),
+                1 => st.update(x..=y, k),
+                2 => ans.push_str(&format!("{}\n", st.query(x..=y))),
                 _ => unreachable!(),
             }
         }
@@ -279,12 +457,78 @@ mod tests {
             let (op, x, y, k): (usize, usize, usize, isize);
             fscanln!(reader, op, x, y, k; ?);
             match op {
-                1 => st.modify(1, 1, n, x, y, (0, k)), // mul
-                2 => st.modify(1, 1, n, x, y, (k, 1)), // add
-                3 => ans.push_str(&format!("{}\n", st.query(1, 1, n, x, y))),
+                1 => st.update(x..=y, (0, k)), // mul
+                2 => st.update(x..=y, (k, 1)), // add
+                3 => ans.push_str(&format!("{}\n", st.query(x..=y))),
                 _ => unreachable!(),
             }
         }
         assert_eq!(ans, "17\n40\n".to_string());
     }
+
+    #[test]
+    fn point_and_range() {
+        let mut v: Vec<i64> = (0..=10).map(|i| i * i % 7).collect();
+        let mut st = SegmentTree::<i64, i64, AddSum>::from(v.clone());
+        st.set(3, -5);
+        v[3] = -5;
+        st.update(2..8, 3);
+        v[2..8].iter_mut().for_each(|x| *x += 3);
+        st.update(5.., -1);
+        v[5..].iter_mut().for_each(|x| *x -= 1);
+        st.set(6, 100);
+        v[6] = 100;
+        for i in 1..=10 {
+            assert_eq!(st.get(i), v[i]);
+            for j in i..=10 {
+                assert_eq!(st.query(i..=j), v[i..=j].iter().sum::<i64>());
+            }
+        }
+        assert_eq!(st.query(4..4), 0);
+        assert_eq!(st.query(..), v.iter().sum::<i64>());
+    }
+
+    #[test]
+    #[should_panic(expected = "Query out of bounds: the range is 1..=3 but the query is 2..5")]
+    fn query_out_of_bounds() {
+        let mut st = SegmentTree::<i64, i64, AddSum>::from(vec![0, 1, 2, 3]);
+        st.query(2..=4);
+    }
+
+    #[test]
+    fn binary_search() {
+        use crate::data_structure::segment_tree::AddMax;
+
+        let mut v: Vec<i64> = (0..=20).map(|i| i * 7 % 11 + 1).collect();
+        let mut sum = SegmentTree::<i64, i64, AddSum>::from(v.clone());
+        let mut max = SegmentTree::<i64, i64, AddMax>::from(v.clone());
+        for round in 0..4 {
+            for l in 1..=21 {
+                for k in [0, 5, 17, 60, 1000] {
+                    let r = (l..=20)
+                        .take_while(|&r| v[l..=r].iter().sum::<i64>() <= k)
+                        .last()
+                        .unwrap_or(l - 1);
+                    assert_eq!(sum.max_right(l, |s| s <= k), r);
+                    let first = (l..=20).find(|&i| v[i] >= k / 60 + 5).unwrap_or(21);
+                    assert_eq!(max.max_right(l, |m| m < k / 60 + 5) + 1, first);
+                }
+            }
+            for r in 0..=20 {
+                for k in [0, 5, 17, 60, 1000] {
+                    let l = (1..=r)
+                        .rev()
+                        .take_while(|&l| v[l..=r].iter().sum::<i64>() <= k)
+                        .last()
+                        .unwrap_or(r + 1);
+                    assert_eq!(sum.min_left(r, |s| s <= k), l);
+                }
+            }
+            sum.update(round + 3..=round * 4 + 6, 2);
+            max.update(round + 3..=round * 4 + 6, 2);
+            v[round + 3..=round * 4 + 6]
+                .iter_mut()
+                .for_each(|x| *x += 2);
+        }
+    }
 }
@@ -1,12 +1,12 @@
-use crate::num::{Numeric, NumericOps};
+use crate::num::{Bounded, IntegerOps, Numeric, NumericCmpOps, NumericOps};
+use crate::number_theory::gcd;
 use std::convert::TryFrom;
-use std::fmt::Debug;
 use std::marker::PhantomData;
 
 /// The abstract operation for segment tree.
 pub trait Operation<V, T>
 where
-    V: Clone + Copy,
+    V: Clone,
     T: Clone + Copy,
 {
     /// Combines two values from left and right child.
@@ -25,26 +25,240 @@ where
     const TAG_IDENTITY: T;
 }
 
+/// The abstract monoid for segment trees without range updates.
+///
+/// `COMBINE` must be associative with identity `IDENTITY`.
+pub trait Monoid<V>
+where
+    V: Clone,
+{
+    /// Combines two values from left and right child.
+    const COMBINE: fn(left_val: V, right_val: V) -> V;
+
+    /// Identity value for the buffer.
+    const IDENTITY: V;
+}
+
+/// Returns `x` added `len` times.
+///
+/// When `len` does not fit in `V`, as in a [`DynamicSegmentTree`](super::DynamicSegmentTree)
+/// of `i32` over a huge range, it falls back to doubling, so the result only overflows
+/// if the sum itself does.
+fn times<V>(x: V, len: usize) -> V
+where
+    V: Numeric + NumericOps + Copy + TryFrom<usize>,
+{
+    if let Ok(len) = V::try_from(len) {
+        return x * len;
+    }
+    let (mut x, mut len, mut sum) = (x, len, V::ZERO);
+    while len > 0 {
+        if len & 1 == 1 {
+            sum = sum + x;
+        }
+        len >>= 1;
+        if len > 0 {
+            x = x + x;
+        }
+    }
+    sum
+}
+
 #[derive(Debug)]
 pub struct AddSum;
 
 impl<V> Operation<V, V> for AddSum
 where
     V: Numeric + NumericOps + Clone + Copy + TryFrom<usize>,
-    <V as TryFrom<usize>>::Error: Debug,
 {
     const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val + right_val;
-    const PUSH_VAL: fn(V, V, usize) -> V = |val, tag, len| val + tag * V::try_from(len).unwrap();
+    const PUSH_VAL: fn(V, V, usize) -> V = |val, tag, len| val + times(tag, len);
+    const PUSH_TAG: fn(V, V) -> V = |child_tag, tag| child_tag + tag;
+    const TAG_IDENTITY: V = V::ZERO;
+    const VAL_IDENTITY: V = V::ZERO;
+}
+
+impl<V> Monoid<V> for AddSum
+where
+    V: Numeric + NumericOps + Clone,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val + right_val;
+    const IDENTITY: V = V::ZERO;
+}
+
+/// Range add, range min.
+#[derive(Debug)]
+pub struct AddMin;
+
+impl<V> Operation<V, V> for AddMin
+where
+    V: Numeric + NumericOps + NumericCmpOps + Bounded + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val.min(right_val);
+    const PUSH_VAL: fn(V, V, usize) -> V = |val, tag, _| val + tag;
+    const PUSH_TAG: fn(V, V) -> V = |child_tag, tag| child_tag + tag;
+    const TAG_IDENTITY: V = V::ZERO;
+    const VAL_IDENTITY: V = V::MAX;
+}
+
+impl<V> Monoid<V> for AddMin
+where
+    V: NumericCmpOps + Bounded + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val.min(right_val);
+    const IDENTITY: V = V::MAX;
+}
+
+/// Range add, range max.
+#[derive(Debug)]
+pub struct AddMax;
+
+impl<V> Operation<V, V> for AddMax
+where
+    V: Numeric + NumericOps + NumericCmpOps + Bounded + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val.max(right_val);
+    const PUSH_VAL: fn(V, V, usize) -> V = |val, tag, _| val + tag;
     const PUSH_TAG: fn(V, V) -> V = |child_tag, tag| child_tag + tag;
     const TAG_IDENTITY: V = V::ZERO;
+    const VAL_IDENTITY: V = V::MIN;
+}
+
+impl<V> Monoid<V> for AddMax
+where
+    V: NumericCmpOps + Bounded + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val.max(right_val);
+    const IDENTITY: V = V::MIN;
+}
+
+/// Range assign, range sum. The tag `None` assigns nothing.
+#[derive(Debug)]
+pub struct AssignSum;
+
+impl<V> Operation<V, Option<V>> for AssignSum
+where
+    V: Numeric + NumericOps + Clone + Copy + TryFrom<usize>,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val + right_val;
+    const PUSH_VAL: fn(V, Option<V>, usize) -> V = |val, tag, len| match tag {
+        Some(tag) => times(tag, len),
+        None => val,
+    };
+    const PUSH_TAG: fn(Option<V>, Option<V>) -> Option<V> = |child_tag, tag| tag.or(child_tag);
+    const TAG_IDENTITY: Option<V> = None;
     const VAL_IDENTITY: V = V::ZERO;
 }
 
-/// Cartesian product of two operations.
+/// Range assign, range min. The tag `None` assigns nothing.
+#[derive(Debug)]
+pub struct AssignMin;
+
+impl<V> Operation<V, Option<V>> for AssignMin
+where
+    V: NumericCmpOps + Bounded + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val.min(right_val);
+    const PUSH_VAL: fn(V, Option<V>, usize) -> V = |val, tag, _| tag.unwrap_or(val);
+    const PUSH_TAG: fn(Option<V>, Option<V>) -> Option<V> = |child_tag, tag| tag.or(child_tag);
+    const TAG_IDENTITY: Option<V> = None;
+    const VAL_IDENTITY: V = V::MAX;
+}
+
+/// Range assign, range max. The tag `None` assigns nothing.
+#[derive(Debug)]
+pub struct AssignMax;
+
+impl<V> Operation<V, Option<V>> for AssignMax
+where
+    V: NumericCmpOps + Bounded + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val.max(right_val);
+    const PUSH_VAL: fn(V, Option<V>, usize) -> V = |val, tag, _| tag.unwrap_or(val);
+    const PUSH_TAG: fn(Option<V>, Option<V>) -> Option<V> = |child_tag, tag| tag.or(child_tag);
+    const TAG_IDENTITY: Option<V> = None;
+    const VAL_IDENTITY: V = V::MIN;
+}
+
+/// Range affine map `x -> a * x + b` with the tag `(a, b)`, range sum.
+///
+/// Use a [`ModInt`](crate::num::ModInt) to work modulo `p`.
+#[derive(Debug)]
+pub struct AffineSum;
+
+impl<V> Operation<V, (V, V)> for AffineSum
+where
+    V: Numeric + NumericOps + Clone + Copy + TryFrom<usize>,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val + right_val;
+    const PUSH_VAL: fn(V, (V, V), usize) -> V = |val, (a, b), len| a * val + times(b, len);
+    const PUSH_TAG: fn((V, V), (V, V)) -> (V, V) =
+        |(child_a, child_b), (a, b)| (a * child_a, a * child_b + b);
+    const TAG_IDENTITY: (V, V) = (V::ONE, V::ZERO);
+    const VAL_IDENTITY: V = V::ZERO;
+}
+
+/// Range chmin `x -> min(x, tag)`, range max.
+#[derive(Debug)]
+pub struct ChminMax;
+
+impl<V> Operation<V, V> for ChminMax
+where
+    V: NumericCmpOps + Bounded + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val.max(right_val);
+    const PUSH_VAL: fn(V, V, usize) -> V = |val, tag, _| val.min(tag);
+    const PUSH_TAG: fn(V, V) -> V = |child_tag, tag| child_tag.min(tag);
+    const TAG_IDENTITY: V = V::MAX;
+    const VAL_IDENTITY: V = V::MIN;
+}
+
+/// Range chmax `x -> max(x, tag)`, range min.
+#[derive(Debug)]
+pub struct ChmaxMin;
+
+impl<V> Operation<V, V> for ChmaxMin
+where
+    V: NumericCmpOps + Bounded + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = |left_val, right_val| left_val.min(right_val);
+    const PUSH_VAL: fn(V, V, usize) -> V = |val, tag, _| val.max(tag);
+    const PUSH_TAG: fn(V, V) -> V = |child_tag, tag| child_tag.max(tag);
+    const TAG_IDENTITY: V = V::MIN;
+    const VAL_IDENTITY: V = V::MAX;
+}
+
+/// Range gcd, without range updates.
+#[derive(Debug)]
+pub struct Gcd;
+
+impl<V> Operation<V, ()> for Gcd
+where
+    V: Numeric + IntegerOps + NumericCmpOps + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = gcd;
+    const PUSH_VAL: fn(V, (), usize) -> V = |val, _, _| val;
+    const PUSH_TAG: fn((), ()) -> () = |_, _| ();
+    const TAG_IDENTITY: () = ();
+    const VAL_IDENTITY: V = V::ZERO;
+}
+
+impl<V> Monoid<V> for Gcd
+where
+    V: Numeric + IntegerOps + NumericCmpOps + Clone + Copy,
+{
+    const COMBINE: fn(V, V) -> V = gcd;
+    const IDENTITY: V = V::ZERO;
+}
+
+/// Cartesian product of two operations, maintaining both values in one tree.
+///
+/// A tuple of operations like `(O1, O2)` does the same without naming the value and tag types.
+#[allow(clippy::type_complexity)]
 pub struct OperationPair<V1, V2, T1, T2, O1, O2>
 where
-    V1: Clone + Copy,
-    V2: Clone + Copy,
+    V1: Clone,
+    V2: Clone,
     T1: Clone + Copy,
     T2: Clone + Copy,
     O1: Operation<V1, T1>,
@@ -60,38 +274,210 @@ where
     ),
 }
 
-impl<V1, V2, T1, T2, O1, O2> OperationPair<V1, V2, T1, T2, O1, O2>
+#[allow(clippy::type_complexity)]
+impl<V1, V2, T1, T2, O1, O2> Operation<(V1, V2), (T1, T2)> for OperationPair<V1, V2, T1, T2, O1, O2>
 where
-    V1: Clone + Copy,
-    V2: Clone + Copy,
+    V1: Clone,
+    V2: Clone,
     T1: Clone + Copy,
     T2: Clone + Copy,
     O1: Operation<V1, T1>,
     O2: Operation<V2, T2>,
 {
-    const COMBINE: fn(left_val: (V1, V2), right_val: (V1, V2)) -> (V1, V2) =
-        |left_val, right_val| {
-            (
-                O1::COMBINE(left_val.0, right_val.0),
-                O2::COMBINE(left_val.1, right_val.1),
-            )
-        };
+    const COMBINE: fn((V1, V2), (V1, V2)) -> (V1, V2) =
+        <(O1, O2) as Operation<(V1, V2), (T1, T2)>>::COMBINE;
+    const PUSH_VAL: fn((V1, V2), (T1, T2), usize) -> (V1, V2) =
+        <(O1, O2) as Operation<(V1, V2), (T1, T2)>>::PUSH_VAL;
+    const PUSH_TAG: fn((T1, T2), (T1, T2)) -> (T1, T2) =
+        <(O1, O2) as Operation<(V1, V2), (T1, T2)>>::PUSH_TAG;
+    const TAG_IDENTITY: (T1, T2) = (O1::TAG_IDENTITY, O2::TAG_IDENTITY);
+    const VAL_IDENTITY: (V1, V2) = (O1::VAL_IDENTITY, O2::VAL_IDENTITY);
+}
 
-    const PUSH_VAL: fn((V1, V2), (T1, T2), usize) -> (V1, V2) = |val, tag, len| {
-        (
-            O1::PUSH_VAL(val.0, tag.0, len),
-            O2::PUSH_VAL(val.1, tag.1, len),
-        )
+macro_rules! tuple_operation_impl {
+    ( $( $o:ident $v:ident $t:ident $i:tt )+ ) => {
+        impl<$($o, $v, $t),+> Operation<($($v,)+), ($($t,)+)> for ($($o,)+)
+        where
+            $($v: Clone, $t: Clone + Copy, $o: Operation<$v, $t>,)+
+        {
+            const COMBINE: fn(($($v,)+), ($($v,)+)) -> ($($v,)+) =
+                |left_val, right_val| ($($o::COMBINE(left_val.$i, right_val.$i),)+);
+            const PUSH_VAL: fn(($($v,)+), ($($t,)+), usize) -> ($($v,)+) =
+                |val, tag, len| ($($o::PUSH_VAL(val.$i, tag.$i, len),)+);
+            const PUSH_TAG: fn(($($t,)+), ($($t,)+)) -> ($($t,)+) =
+                |child_tag, tag| ($($o::PUSH_TAG(child_tag.$i, tag.$i),)+);
+            const TAG_IDENTITY: ($($t,)+) = ($($o::TAG_IDENTITY,)+);
+            const VAL_IDENTITY: ($($v,)+) = ($($o::VAL_IDENTITY,)+);
+        }
     };
+}
+
+tuple_operation_impl!(O1 V1 T1 0 O2 V2 T2 1);
+tuple_operation_impl!(O1 V1 T1 0 O2 V2 T2 1 O3 V3 T3 2);
+tuple_operation_impl!(O1 V1 T1 0 O2 V2 T2 1 O3 V3 T3 2 O4 V4 T4 3);
 
-    const PUSH_TAG: fn((T1, T2), (T1, T2)) -> (T1, T2) = |child_tag, tag| {
-        (
-            O1::PUSH_TAG(child_tag.0, tag.0),
-            O2::PUSH_TAG(child_tag.1, tag.1),
-        )
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::segment_tree::{
+        AddMax, AddMin, AddSum, AffineSum, AssignMax, AssignMin, AssignSum, ChmaxMin, ChminMax,
+        Gcd, Operation, OperationPair,
     };
+    use crate::data_structure::{BottomUpSegmentTree, SegmentTree};
+    use crate::fscanln;
+    use crate::num::ModInt998244353;
+    use crate::utils::xorshift::Xorshift;
+    use std::io::{BufReader, Cursor};
 
-    const TAG_IDENTITY: (T1, T2) = (O1::TAG_IDENTITY, O2::TAG_IDENTITY);
+    /// A small deterministic generator of `(l, r, x)` with `1 <= l <= r <= n`.
+    fn queries(n: usize, count: usize) -> Vec<(usize, usize, i64)> {
+        let mut rng = Xorshift::new(0x9e3779b97f4a7c15);
+        (0..count)
+            .map(|_| {
+                let (a, b) = (rng.next(n) + 1, rng.next(n) + 1);
+                (a.min(b), a.max(b), rng.next(201) as i64 - 100)
+            })
+            .collect()
+    }
 
-    const VAL_IDENTITY: (V1, V2) = (O1::VAL_IDENTITY, O2::VAL_IDENTITY);
+    /// Checks `O` against a plain array, applying `apply` and folding with `fold`.
+    fn brute_force<V, T, O>(
+        init: Vec<V>,
+        tag: impl Fn(i64) -> T,
+        apply: impl Fn(V, i64) -> V,
+        fold: impl Fn(&[V]) -> V,
+    ) where
+        V: Copy + PartialEq + std::fmt::Debug,
+        T: Copy + PartialEq,
+        O: Operation<V, T>,
+    {
+        let n = init.len() - 1;
+        let mut v = init.clone();
+        let mut st = SegmentTree::<V, T, O>::from(init);
+        for (i, (l, r, x)) in queries(n, 500).into_iter().enumerate() {
+            if i % 2 == 0 {
+                st.update(l..=r, tag(x));
+                v[l..=r].iter_mut().for_each(|y| *y = apply(*y, x));
+            } else {
+                assert_eq!(st.query(l..=r), fold(&v[l..=r]));
+            }
+        }
+    }
+
+    fn init(n: usize) -> Vec<i64> {
+        (0..=n as i64).map(|i| i * 37 % 23 - 11).collect()
+    }
+
+    #[test]
+    fn add_min_max() {
+        let min = |s: &[i64]| *s.iter().min().unwrap();
+        let max = |s: &[i64]| *s.iter().max().unwrap();
+        brute_force::<_, _, AddMin>(init(50), |x| x, |y, x| y + x, min);
+        brute_force::<_, _, AddMax>(init(50), |x| x, |y, x| y + x, max);
+    }
+
+    #[test]
+    fn assign() {
+        let sum = |s: &[i64]| s.iter().sum();
+        let min = |s: &[i64]| *s.iter().min().unwrap();
+        let max = |s: &[i64]| *s.iter().max().unwrap();
+        brute_force::<_, _, AssignSum>(init(50), Some, |_, x| x, sum);
+        brute_force::<_, _, AssignMin>(init(50), Some, |_, x| x, min);
+        brute_force::<_, _, AssignMax>(init(50), Some, |_, x| x, max);
+    }
+
+    #[test]
+    fn affine() {
+        type Mint = ModInt998244353;
+        let init: Vec<Mint> = init(50).into_iter().map(Mint::from).collect();
+        // Alternates between `x -> k x + 1` and `x -> x + k` depending on the sign of `k`.
+        let tag = |k: i64| {
+            if k < 0 {
+                (Mint::from(1), Mint::from(k))
+            } else {
+                (Mint::from(k), Mint::from(1))
+            }
+        };
+        let apply = move |y: Mint, k: i64| {
+            let (a, b) = tag(k);
+            a * y + b
+        };
+        brute_force::<_, _, AffineSum>(init, tag, apply, |s| s.iter().copied().sum());
+    }
+
+    #[test]
+    fn chmin_chmax() {
+        let min = |s: &[i64]| *s.iter().min().unwrap();
+        let max = |s: &[i64]| *s.iter().max().unwrap();
+        brute_force::<_, _, ChminMax>(init(50), |x| x, |y, x| y.min(x), max);
+        brute_force::<_, _, ChmaxMin>(init(50), |x| x, |y, x| y.max(x), min);
+    }
+
+    #[test]
+    fn luogu_p1890() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+5 3
+4 12 3 6 7
+1 3
+2 3
+5 5
+"#,
+        ));
+
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let v: Vec<i64>;
+        fscanln!(reader, v; n);
+        let st = BottomUpSegmentTree::<i64, Gcd>::from(v.clone());
+        let mut lazy = SegmentTree::<i64, (), Gcd>::from(v);
+        let mut ans = vec![];
+        for _ in 0..m {
+            let (l, r): (usize, usize);
+            fscanln!(reader, l, r);
+            assert_eq!(lazy.query(l..=r), st.query(l..=r));
+            ans.push(st.query(l..=r));
+        }
+        assert_eq!(ans, vec![1, 3, 7]);
+    }
+
+    #[test]
+    fn products() {
+        type SumMax = OperationPair<i64, i64, i64, i64, AddSum, AddMax>;
+        let pair = |x: i64| (x, x);
+        brute_force::<_, _, SumMax>(
+            init(50).into_iter().map(pair).collect(),
+            pair,
+            |(s, m), x| (s + x, m + x),
+            |s| {
+                (
+                    s.iter().map(|p| p.0).sum(),
+                    s.iter().map(|p| p.1).max().unwrap(),
+                )
+            },
+        );
+
+        let triple = |x: i64| (x, x, x);
+        brute_force::<_, _, (AddSum, AddMin, AddMax)>(
+            init(50).into_iter().map(triple).collect(),
+            triple,
+            |(s, a, b), x| (s + x, a + x, b + x),
+            |s| {
+                (
+                    s.iter().map(|p| p.0).sum(),
+                    s.iter().map(|p| p.1).min().unwrap(),
+                    s.iter().map(|p| p.2).max().unwrap(),
+                )
+            },
+        );
+
+        // Sum and max under range assignment, with a gcd that is never updated.
+        type Quad = (AssignSum, AssignMax, Gcd, AddSum);
+        let mut st =
+            SegmentTree::<(i64, i64, i64, i64), (Option<i64>, Option<i64>, (), i64), Quad>::from(
+                (0..=6).map(|i| (i, i, 2 * i, 0)).collect::<Vec<_>>(),
+            );
+        st.update(2..=3, (Some(10), Some(10), (), 1));
+        assert_eq!(st.query(..), (36, 10, 2, 2));
+        assert_eq!(st.query(3..=4), (14, 10, 2, 1));
+    }
 }
@@ -0,0 +1,451 @@
+use crate::data_structure::segment_tree::{AddSum, Operation};
+use crate::data_structure::to_range;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+
+/// Persistent segment tree keeping every version after each update.
+///
+/// Each update copies the _O(log n)_ nodes on its path and returns the id of the new version,
+/// which shares the untouched nodes with the version it was made from.
+/// Version `0` is the tree built by [`From`], and any version can be queried or updated later.
+///
+/// Range updates keep their tags in the copied nodes, so queries never modify the tree.
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::segment_tree::{AddSum, PersistentSegmentTree};
+///
+/// let mut st = PersistentSegmentTree::<i64, i64, AddSum>::from(vec![0, 1, 2, 3, 4]);
+/// let v1 = st.set(0, 2, 10);
+/// let v2 = st.update(v1, 1..=3, 1);
+/// assert_eq!(st.query(0, ..), 10);
+/// assert_eq!(st.query(v1, ..), 18);
+/// assert_eq!(st.query(v2, 2..), 19);
+/// ```
+#[derive(Debug)]
+pub struct PersistentSegmentTree<V, T, O>
+where
+    V: Clone + Copy,
+    T: Clone + Copy,
+    O: Operation<V, T>,
+{
+    /// Node 0 is a buffer.
+    nodes: Vec<Node<V, T>>,
+    roots: Vec<usize>,
+    len: usize,
+    phantom: PhantomData<O>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node<V, T> {
+    val: V,
+    /// Applied to `val` but not to the children yet.
+    tag: T,
+    left: usize,
+    right: usize,
+}
+
+impl<V, T, O> PersistentSegmentTree<V, T, O>
+where
+    V: Clone + Copy,
+    T: Clone + Copy + PartialEq,
+    O: Operation<V, T>,
+{
+    /// The length of the segment tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the segment tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of versions, the last one being `versions() - 1`.
+    pub fn versions(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Sets the element at `index` to `val` in `version`, and returns the new version.
+    /// Complexity: _O(log n)_.
+    pub fn set(&mut self, version: usize, index: usize, val: V) -> usize {
+        let root = self.root(version);
+        if !(1..=self.len).contains(&index) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len, index
+            );
+        }
+        let root = self.assign(root, 1, self.len, index, val);
+        self.roots.push(root);
+        self.roots.len() - 1
+    }
+
+    /// Returns the element at `index` in `version`.
+    /// Complexity: _O(log n)_.
+    pub fn get(&self, version: usize, index: usize) -> V {
+        if !(1..=self.len).contains(&index) {
+            panic!(
+                "Index out of bounds: the range is 1..={} but the index is {}",
+                self.len, index
+            );
+        }
+        self.query(version, index..=index)
+    }
+
+    /// Applies `tag` to the elements in the range `bounds` of `version`,
+    /// and returns the new version.
+    /// Complexity: _O(log n)_.
+    pub fn update(&mut self, version: usize, bounds: impl RangeBounds<usize>, tag: T) -> usize {
+        let root = self.root(version);
+        let (start, end) = to_range(bounds, self.len);
+        let root = if start < end {
+            self.modify(root, 1, self.len, start, end - 1, tag)
+        } else {
+            root
+        };
+        self.roots.push(root);
+        self.roots.len() - 1
+    }
+
+    /// Returns the combined value of the elements in the range `bounds` of `version`,
+    /// or `O::VAL_IDENTITY` if it is empty.
+    /// Complexity: _O(log n)_.
+    pub fn query(&self, version: usize, bounds: impl RangeBounds<usize>) -> V {
+        let root = self.root(version);
+        let (start, end) = to_range(bounds, self.len);
+        if start < end {
+            self.fold(root, 1, self.len, start, end - 1)
+        } else {
+            O::VAL_IDENTITY
+        }
+    }
+
+    fn root(&self, version: usize) -> usize {
+        if version >= self.roots.len() {
+            panic!(
+                "Version out of bounds: the range is 0..{} but the version is {}",
+                self.roots.len(),
+                version
+            );
+        }
+        self.roots[version]
+    }
+
+    /// Copies the node `x` with `tag` applied, and returns the copy.
+    fn apply(&mut self, x: usize, tag: T, len: usize) -> usize {
+        let mut node = self.nodes[x];
+        node.val = O::PUSH_VAL(node.val, tag, len);
+        node.tag = O::PUSH_TAG(node.tag, tag);
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Copies the node `x` with its tag pushed down to copies of its children.
+    fn pushdown(&mut self, x: usize, l: usize, r: usize) -> Node<V, T> {
+        let mut node = self.nodes[x];
+        if node.tag != O::TAG_IDENTITY {
+            let m = (l + r) >> 1;
+            node.left = self.apply(node.left, node.tag, m - l + 1);
+            node.right = self.apply(node.right, node.tag, r - m);
+            node.tag = O::TAG_IDENTITY;
+        }
+        node
+    }
+
+    fn push(&mut self, mut node: Node<V, T>) -> usize {
+        node.val = O::COMBINE(self.nodes[node.left].val, self.nodes[node.right].val);
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    fn assign(&mut self, x: usize, l: usize, r: usize, index: usize, val: V) -> usize {
+        if l == r {
+            self.nodes.push(Node {
+                val,
+                tag: O::TAG_IDENTITY,
+                left: 0,
+                right: 0,
+            });
+            return self.nodes.len() - 1;
+        }
+        let mut node = self.pushdown(x, l, r);
+        let m = (l + r) >> 1;
+        if index <= m {
+            node.left = self.assign(node.left, l, m, index, val);
+        } else {
+            node.right = self.assign(node.right, m + 1, r, index, val);
+        }
+        self.push(node)
+    }
+
+    fn modify(&mut self, x: usize, l: usize, r: usize, ql: usize, qr: usize, delta: T) -> usize {
+        if ql <= l && r <= qr {
+            return self.apply(x, delta, r - l + 1);
+        }
+        let mut node = self.pushdown(x, l, r);
+        let m = (l + r) >> 1;
+        if ql <= m {
+            node.left = self.modify(node.left, l, m, ql, qr, delta);
+        }
+        if m < qr {
+            node.right = self.modify(node.right, m + 1, r, ql, qr, delta);
+        }
+        self.push(node)
+    }
+
+    fn fold(&self, x: usize, l: usize, r: usize, ql: usize, qr: usize) -> V {
+        let node = &self.nodes[x];
+        if ql <= l && r <= qr {
+            return node.val;
+        }
+        let m = (l + r) >> 1;
+        let val = match (ql <= m, m < qr) {
+            (true, true) => O::COMBINE(
+                self.fold(node.left, l, m, ql, qr),
+                self.fold(node.right, m + 1, r, ql, qr),
+            ),
+            (true, false) => self.fold(node.left, l, m, ql, qr),
+            (false, true) => self.fold(node.right, m + 1, r, ql, qr),
+            (false, false) => unreachable!(),
+        };
+        // The tag of a partially covered node still applies to its children.
+        O::PUSH_VAL(val, node.tag, qr.min(r) - ql.max(l) + 1)
+    }
+
+    fn build(&mut self, l: usize, r: usize, a: &[V]) -> usize {
+        if l == r {
+            self.nodes.push(Node {
+                val: a[l],
+                tag: O::TAG_IDENTITY,
+                left: 0,
+                right: 0,
+            });
+            return self.nodes.len() - 1;
+        }
+        let m = (l + r) >> 1;
+        let node = Node {
+            val: O::VAL_IDENTITY,
+            tag: O::TAG_IDENTITY,
+            left: self.build(l, m, a),
+            right: self.build(m + 1, r, a),
+        };
+        self.push(node)
+    }
+}
+
+impl<V, T, O, Q> From<Q> for PersistentSegmentTree<V, T, O>
+where
+    V: Clone + Copy,
+    T: Clone + Copy + PartialEq,
+    O: Operation<V, T>,
+    Q: Into<Vec<V>>,
+{
+    /// Constructs version `0` from a 1-based collection.
+    ///
+    /// Complexity: _O(n)_.
+    fn from(a: Q) -> Self {
+        let v = a.into();
+        let len = v.len() - 1;
+        let mut st = PersistentSegmentTree {
+            nodes: Vec::with_capacity(len << 1),
+            roots: vec![],
+            len,
+            phantom: PhantomData,
+        };
+        st.nodes.push(Node {
+            val: O::VAL_IDENTITY,
+            tag: O::TAG_IDENTITY,
+            left: 0,
+            right: 0,
+        });
+        let root = if len > 0 { st.build(1, len, &v) } else { 0 };
+        st.roots.push(root);
+        st
+    }
+}
+
+/// K-th smallest element of any range of a fixed array, by a persistent segment tree of counts.
+///
+/// Version `i` counts the values of the first `i` elements, so the counts of a range are
+/// the difference of two versions, and the k-th smallest is found by descending both at once.
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::segment_tree::KthSmallest;
+///
+/// let ks = KthSmallest::from(vec![0, 25957, 6405, 15770, 26287, 26465]);
+/// assert_eq!(ks.kth_in_range(2, 2, 1), 6405);
+/// assert_eq!(ks.kth_in_range(1, 5, 3), 25957);
+/// ```
+#[derive(Debug)]
+pub struct KthSmallest<T> {
+    /// The distinct values in increasing order.
+    sorted: Vec<T>,
+    counts: PersistentSegmentTree<usize, usize, AddSum>,
+}
+
+impl<T> KthSmallest<T>
+where
+    T: Ord + Clone,
+{
+    /// Returns the `k`-th smallest element of the range `l..=r`, counted from `1`.
+    /// Complexity: _O(log n)_.
+    pub fn kth_in_range(&self, l: usize, r: usize, k: usize) -> T {
+        let n = self.counts.versions() - 1;
+        if !(1..=n).contains(&l) || !(l..=n).contains(&r) || !(1..=r - l + 1).contains(&k) {
+            panic!(
+                "Query out of bounds: the range is 1..={} but the query is the {}-th of {}..={}",
+                n, k, l, r
+            );
+        }
+        let nodes = &self.counts.nodes;
+        let (mut lo, mut hi) = (self.counts.roots[l - 1], self.counts.roots[r]);
+        let (mut left, mut right, mut k) = (1, self.sorted.len(), k);
+        while left < right {
+            let mid = (left + right) >> 1;
+            let count = nodes[nodes[hi].left].val - nodes[nodes[lo].left].val;
+            if k <= count {
+                (lo, hi, right) = (nodes[lo].left, nodes[hi].left, mid);
+            } else {
+                (lo, hi, left) = (nodes[lo].right, nodes[hi].right, mid + 1);
+                k -= count;
+            }
+        }
+        self.sorted[left - 1].clone()
+    }
+}
+
+impl<T, Q> From<Q> for KthSmallest<T>
+where
+    T: Ord + Clone,
+    Q: Into<Vec<T>>,
+{
+    /// Constructs from a 1-based collection.
+    ///
+    /// Complexity: _O(n log n)_.
+    fn from(a: Q) -> Self {
+        let a = a.into();
+        let mut sorted = a[1..].to_vec();
+        sorted.sort();
+        sorted.dedup();
+        let mut counts = PersistentSegmentTree::from(vec![0; sorted.len() + 1]);
+        for (i, x) in a.iter().enumerate().skip(1) {
+            let p = sorted.binary_search(x).unwrap() + 1;
+            let c = counts.get(i - 1, p);
+            counts.set(i - 1, p, c + 1);
+        }
+        Self { sorted, counts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::segment_tree::{
+        AddSum, AffineSum, KthSmallest, PersistentSegmentTree,
+    };
+    use crate::fscanln;
+    use crate::num::ModInt998244353;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p3919() {
+        // Persistent array: each operation makes a version from an older one.
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+5 10
+59 46 14 87 41
+0 2 1
+0 1 1 14
+0 1 1 57
+0 1 1 88
+4 2 4
+0 2 5
+0 2 4
+4 2 1
+2 2 2
+1 1 5 91
+"#,
+        ));
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let v: Vec<i64>;
+        fscanln!(reader, v; n);
+        let mut st = PersistentSegmentTree::<i64, i64, AddSum>::from(v);
+        let mut version = vec![0];
+        let mut ans = vec![];
+        for _ in 0..m {
+            let (ver, op, loc, val): (usize, usize, usize, i64);
+            fscanln!(reader, ver, op, loc, val; ?);
+            match op {
+                1 => version.push(st.set(version[ver], loc, val)),
+                2 => {
+                    ans.push(st.get(version[ver], loc));
+                    // Querying makes a copy of the version.
+                    version.push(version[ver]);
+                }
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(ans, vec![59, 87, 41, 87, 88, 46]);
+    }
+
+    #[test]
+    fn lazy_versions() {
+        type Mint = ModInt998244353;
+        let n = 9;
+        let mut arrays = vec![(0..=n).map(Mint::from).collect::<Vec<_>>()];
+        let mut st = PersistentSegmentTree::<_, _, AffineSum>::from(arrays[0].clone());
+        for step in 1..40_usize {
+            let base = step * 7 % arrays.len();
+            let (l, r) = (step % n + 1, (step * 5 % n + 1).max(step % n + 1));
+            let mut a = arrays[base].clone();
+            let version = if step % 3 == 0 {
+                a[l] = Mint::from(step);
+                st.set(base, l, a[l])
+            } else {
+                let (mul, add) = (Mint::from(step % 4 + 1), Mint::from(step));
+                a[l..=r].iter_mut().for_each(|x| *x = mul * *x + add);
+                st.update(base, l..=r, (mul, add))
+            };
+            assert_eq!(version, arrays.len());
+            arrays.push(a);
+            for (ver, a) in arrays.iter().enumerate() {
+                for i in 1..=n {
+                    for j in i..=n {
+                        let sum = a[i..=j].iter().copied().sum::<Mint>();
+                        assert_eq!(st.query(ver, i..=j), sum);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn luogu_p3834() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+5 5
+25957 6405 15770 26287 26465
+2 2 1
+3 4 1
+4 5 1
+1 2 2
+4 4 1
+"#,
+        ));
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let v: Vec<i64>;
+        fscanln!(reader, v; n);
+        let ks = KthSmallest::from(v);
+        let mut ans = vec![];
+        for _ in 0..m {
+            let (l, r, k): (usize, usize, usize);
+            fscanln!(reader, l, r, k);
+            ans.push(ks.kth_in_range(l, r, k));
+        }
+        assert_eq!(ans, vec![6405, 15770, 26287, 25957, 26287]);
+    }
+}
@@ -0,0 +1,386 @@
+use crate::data_structure::to_range;
+use crate::num::{Bounded, Numeric, NumericCmpOps, NumericOps};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+
+/// Segment tree beats (Ji driver segment tree) for range chmin, chmax and add
+/// with range sum, max and min queries.
+///
+/// Each node keeps its maximum, strict second maximum and the count of the maximum
+/// (and the same for the minimum), so a chmin that only lowers the maxima of a node
+/// is applied as a tag, and it only recurses further where it merges distinct values.
+/// The number of distinct values in the nodes bounds the extra work, which gives the
+/// amortized complexity:
+/// - _O((n + q) log n)_ with only chmin, chmax and queries,
+/// - _O((n + q) log^2 n)_ when range add is mixed in.
+///
+/// `T::MIN` and `T::MAX` are used as sentinels and must not appear as elements.
+///
+/// For more information, see [Segment Tree Beats](https://codeforces.com/blog/entry/57319).
+///
+/// # Examples
+/// ```
+/// use cplit::data_structure::SegmentTreeBeats;
+///
+/// let mut st = SegmentTreeBeats::from(vec![0_i64, 1, 6, 3, 9, 4]);
+/// st.chmin(2..=4, 5);
+/// assert_eq!(st.sum(..), 1 + 5 + 3 + 5 + 4);
+/// st.chmax(.., 4);
+/// st.add(1..=2, -10);
+/// assert_eq!(st.min(..), -6);
+/// assert_eq!(st.max(3..), 5);
+/// ```
+#[derive(Debug)]
+pub struct SegmentTreeBeats<T>
+where
+    T: Numeric + NumericOps + NumericCmpOps + Bounded + Copy + TryFrom<usize>,
+    <T as TryFrom<usize>>::Error: Debug,
+{
+    sum: Vec<T>,
+    max: Vec<T>,
+    /// The strict second maximum, `T::MIN` if all values are equal.
+    max2: Vec<T>,
+    max_count: Vec<usize>,
+    min: Vec<T>,
+    /// The strict second minimum, `T::MAX` if all values are equal.
+    min2: Vec<T>,
+    min_count: Vec<usize>,
+    add: Vec<T>,
+    len: usize,
+}
+
+impl<T> SegmentTreeBeats<T>
+where
+    T: Numeric + NumericOps + NumericCmpOps + Bounded + Copy + TryFrom<usize>,
+    <T as TryFrom<usize>>::Error: Debug,
+{
+    /// The length of the segment tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the segment tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Replaces every element `a[i]` in the range `bounds` with `min(a[i], val)`.
+    /// Complexity: amortized _O(log n)_, or _O(log^2 n)_ with [`add`](Self::add).
+    pub fn chmin(&mut self, bounds: impl RangeBounds<usize>, val: T) {
+        let (start, end) = to_range(bounds, self.len);
+        if start < end {
+            self.modify_chmin(1, 1, self.len, start, end - 1, val);
+        }
+    }
+
+    /// Replaces every element `a[i]` in the range `bounds` with `max(a[i], val)`.
+    /// Complexity: amortized _O(log n)_, or _O(log^2 n)_ with [`add`](Self::add).
+    pub fn chmax(&mut self, bounds: impl RangeBounds<usize>, val: T) {
+        let (start, end) = to_range(bounds, self.len);
+        if start < end {
+            self.modify_chmax(1, 1, self.len, start, end - 1, val);
+        }
+    }
+
+    /// Adds `delta` to every element in the range `bounds`.
+    /// Complexity: _O(log n)_.
+    pub fn add(&mut self, bounds: impl RangeBounds<usize>, delta: T) {
+        let (start, end) = to_range(bounds, self.len);
+        if start < end {
+            self.modify_add(1, 1, self.len, start, end - 1, delta);
+        }
+    }
+
+    /// Returns the sum of the elements in the range `bounds`.
+    /// Complexity: _O(log n)_.
+    pub fn sum(&mut self, bounds: impl RangeBounds<usize>) -> T {
+        self.fold(bounds).0
+    }
+
+    /// Returns the maximum of the elements in the range `bounds`, `T::MIN` if it is empty.
+    /// Complexity: _O(log n)_.
+    pub fn max(&mut self, bounds: impl RangeBounds<usize>) -> T {
+        self.fold(bounds).1
+    }
+
+    /// Returns the minimum of the elements in the range `bounds`, `T::MAX` if it is empty.
+    /// Complexity: _O(log n)_.
+    pub fn min(&mut self, bounds: impl RangeBounds<usize>) -> T {
+        self.fold(bounds).2
+    }
+
+    /// The sum, maximum and minimum of the range `bounds`.
+    fn fold(&mut self, bounds: impl RangeBounds<usize>) -> (T, T, T) {
+        let (start, end) = to_range(bounds, self.len);
+        if start < end {
+            self.query(1, 1, self.len, start, end - 1)
+        } else {
+            (T::ZERO, T::MIN, T::MAX)
+        }
+    }
+
+    fn pushup(&mut self, x: usize) {
+        let (l, r) = (x << 1, x << 1 | 1);
+        self.sum[x] = self.sum[l] + self.sum[r];
+
+        let (a, b) = (self.max[l], self.max[r]);
+        self.max[x] = a.max(b);
+        self.max2[x] = match a.cmp(&b) {
+            Ordering::Equal => self.max2[l].max(self.max2[r]),
+            Ordering::Greater => self.max2[l].max(b),
+            Ordering::Less => a.max(self.max2[r]),
+        };
+        self.max_count[x] = (a == self.max[x]) as usize * self.max_count[l]
+            + (b == self.max[x]) as usize * self.max_count[r];
+
+        let (a, b) = (self.min[l], self.min[r]);
+        self.min[x] = a.min(b);
+        self.min2[x] = match a.cmp(&b) {
+            Ordering::Equal => self.min2[l].min(self.min2[r]),
+            Ordering::Less => self.min2[l].min(b),
+            Ordering::Greater => a.min(self.min2[r]),
+        };
+        self.min_count[x] = (a == self.min[x]) as usize * self.min_count[l]
+            + (b == self.min[x]) as usize * self.min_count[r];
+    }
+
+    fn apply_add(&mut self, x: usize, len: usize, delta: T) {
+        self.sum[x] = self.sum[x] + delta * T::try_from(len).unwrap();
+        self.max[x] = self.max[x] + delta;
+        if self.max2[x] != T::MIN {
+            self.max2[x] = self.max2[x] + delta;
+        }
+        self.min[x] = self.min[x] + delta;
+        if self.min2[x] != T::MAX {
+            self.min2[x] = self.min2[x] + delta;
+        }
+        self.add[x] = self.add[x] + delta;
+    }
+
+    /// Lowers the maxima of node `x` to `val`, given `max2[x] < val < max[x]`.
+    fn apply_chmin(&mut self, x: usize, val: T) {
+        let count = T::try_from(self.max_count[x]).unwrap();
+        self.sum[x] = self.sum[x] - (self.max[x] - val) * count;
+        if self.min[x] == self.max[x] {
+            self.min[x] = val;
+        } else if self.min2[x] == self.max[x] {
+            self.min2[x] = val;
+        }
+        self.max[x] = val;
+    }
+
+    /// Raises the minima of node `x` to `val`, given `min[x] < val < min2[x]`.
+    fn apply_chmax(&mut self, x: usize, val: T) {
+        let count = T::try_from(self.min_count[x]).unwrap();
+        self.sum[x] = self.sum[x] + (val - self.min[x]) * count;
+        if self.max[x] == self.min[x] {
+            self.max[x] = val;
+        } else if self.max2[x] == self.min[x] {
+            self.max2[x] = val;
+        }
+        self.min[x] = val;
+    }
+
+    fn pushdown(&mut self, x: usize, l: usize, r: usize) {
+        let m = (l + r) >> 1;
+        for (child, len) in [(x << 1, m - l + 1), (x << 1 | 1, r - m)] {
+            if self.add[x] != T::ZERO {
+                self.apply_add(child, len, self.add[x]);
+            }
+            if self.max[child] > self.max[x] {
+                self.apply_chmin(child, self.max[x]);
+            }
+            if self.min[child] < self.min[x] {
+                self.apply_chmax(child, self.min[x]);
+            }
+        }
+        self.add[x] = T::ZERO;
+    }
+
+    fn modify_chmin(&mut self, x: usize, l: usize, r: usize, ql: usize, qr: usize, val: T) {
+        if self.max[x] <= val {
+            return;
+        }
+        if ql <= l && r <= qr && self.max2[x] < val {
+            self.apply_chmin(x, val);
+            return;
+        }
+        self.pushdown(x, l, r);
+        let m = (l + r) >> 1;
+        if ql <= m {
+            self.modify_chmin(x << 1, l, m, ql, qr, val);
+        }
+        if m < qr {
+            self.modify_chmin(x << 1 | 1, m + 1, r, ql, qr, val);
+        }
+        self.pushup(x);
+    }
+
+    fn modify_chmax(&mut self, x: usize, l: usize, r: usize, ql: usize, qr: usize, val: T) {
+        if self.min[x] >= val {
+            return;
+        }
+        if ql <= l && r <= qr && self.min2[x] > val {
+            self.apply_chmax(x, val);
+            return;
+        }
+        self.pushdown(x, l, r);
+        let m = (l + r) >> 1;
+        if ql <= m {
+            self.modify_chmax(x << 1, l, m, ql, qr, val);
+        }
+        if m < qr {
+            self.modify_chmax(x << 1 | 1, m + 1, r, ql, qr, val);
+        }
+        self.pushup(x);
+    }
+
+    fn modify_add(&mut self, x: usize, l: usize, r: usize, ql: usize, qr: usize, delta: T) {
+        if ql <= l && r <= qr {
+            self.apply_add(x, r - l + 1, delta);
+            return;
+        }
+        self.pushdown(x, l, r);
+        let m = (l + r) >> 1;
+        if ql <= m {
+            self.modify_add(x << 1, l, m, ql, qr, delta);
+        }
+        if m < qr {
+            self.modify_add(x << 1 | 1, m + 1, r, ql, qr, delta);
+        }
+        self.pushup(x);
+    }
+
+    fn query(&mut self, x: usize, l: usize, r: usize, ql: usize, qr: usize) -> (T, T, T) {
+        if ql <= l && r <= qr {
+            return (self.sum[x], self.max[x], self.min[x]);
+        }
+        self.pushdown(x, l, r);
+        let m = (l + r) >> 1;
+        match (ql <= m, m < qr) {
+            (true, true) => {
+                let (s1, max1, min1) = self.query(x << 1, l, m, ql, qr);
+                let (s2, max2, min2) = self.query(x << 1 | 1, m + 1, r, ql, qr);
+                (s1 + s2, max1.max(max2), min1.min(min2))
+            }
+            (true, false) => self.query(x << 1, l, m, ql, qr),
+            (false, true) => self.query(x << 1 | 1, m + 1, r, ql, qr),
+            (false, false) => unreachable!(),
+        }
+    }
+
+    fn init(&mut self, x: usize, l: usize, r: usize, a: &[T]) {
+        if l == r {
+            self.sum[x] = a[l];
+            self.max[x] = a[l];
+            self.min[x] = a[l];
+            self.max_count[x] = 1;
+            self.min_count[x] = 1;
+        } else {
+            let m = (l + r) >> 1;
+            self.init(x << 1, l, m, a);
+            self.init(x << 1 | 1, m + 1, r, a);
+            self.pushup(x);
+        }
+    }
+}
+
+impl<T, Q> From<Q> for SegmentTreeBeats<T>
+where
+    T: Numeric + NumericOps + NumericCmpOps + Bounded + Copy + TryFrom<usize>,
+    <T as TryFrom<usize>>::Error: Debug,
+    Q: Into<Vec<T>>,
+{
+    /// Constructs a new segment tree beats from a 1-based collection.
+    ///
+    /// Complexity: _O(n)_.
+    fn from(a: Q) -> Self {
+        let v = a.into();
+        let len = v.len() - 1;
+        let size = 10 + (len << 2);
+        let mut st = SegmentTreeBeats {
+            sum: vec![T::ZERO; size],
+            max: vec![T::MIN; size],
+            max2: vec![T::MIN; size],
+            max_count: vec![0; size],
+            min: vec![T::MAX; size],
+            min2: vec![T::MAX; size],
+            min_count: vec![0; size],
+            add: vec![T::ZERO; size],
+            len,
+        };
+        if len > 0 {
+            st.init(1, 1, len, &v);
+        }
+        st
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::SegmentTreeBeats;
+    use crate::fscanln;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn hdu_5306() {
+        // Gorgeous Sequence: range chmin, range max and range sum.
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+5 5
+1 2 3 4 5
+1 1 5
+2 1 5
+0 3 5 3
+1 1 5
+2 1 5
+"#,
+        ));
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let v: Vec<i64>;
+        fscanln!(reader, v; n);
+        let mut st = SegmentTreeBeats::from(v);
+        let mut ans = vec![];
+        for _ in 0..m {
+            let (op, x, y, t): (usize, usize, usize, i64);
+            fscanln!(reader, op, x, y, t; ?);
+            match op {
+                0 => st.chmin(x..=y, t),
+                1 => ans.push(st.max(x..=y)),
+                2 => ans.push(st.sum(x..=y)),
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(ans, vec![5, 15, 3, 12]);
+    }
+
+    #[test]
+    fn mixed_updates() {
+        let mut st = SegmentTreeBeats::from(vec![0_i64, 5, -2, 8, 0, 3, 7]);
+        st.chmin(.., 4);
+        // 4 -2 4 0 3 4
+        assert_eq!((st.sum(..), st.max(..), st.min(..)), (13, 4, -2));
+        st.chmax(2..=5, 1);
+        // 4 1 4 1 3 4
+        assert_eq!((st.sum(..), st.max(2..=5), st.min(..)), (17, 4, 1));
+        st.add(3.., -5);
+        // 4 1 -1 -4 -2 -1
+        assert_eq!((st.sum(..), st.max(3..), st.min(..)), (-3, -1, -4));
+        st.chmin(.., 0);
+        st.chmax(4..=4, 10);
+        // 0 0 -1 10 -2 -1
+        assert_eq!((st.sum(..), st.max(..), st.min(..=3)), (6, 10, -1));
+        st.add(.., 3);
+        // 3 3 2 13 1 2
+        assert_eq!((st.sum(..), st.max(5..), st.min(..)), (24, 2, 1));
+        st.chmin(..=4, 2);
+        // 2 2 2 2 1 2
+        assert_eq!(
+            (st.sum(..), st.sum(..=4), st.max(..), st.min(..)),
+            (11, 8, 2, 1)
+        );
+    }
+}
@@ -1,28 +1,30 @@
 //! General purpose algorithms.
 
-use crate::num::{Bounded, Numeric, NumericCmpOps, NumericOps};
+use crate::num::{MaybeBounded, Numeric, NumericCmpOps, NumericOps};
 use std::cmp::Ord;
 use std::ops::{Bound, RangeBounds};
 
 /// Finds the smallest number `x` in the specified `bounds` such that `f(x) == true`.
+///
+/// Panics if a bound is unbounded but the type has no such bound.
 pub fn binary_search<N>(bounds: impl RangeBounds<N>, f: impl Fn(N) -> bool) -> N
 where
-    N: Numeric + NumericOps + NumericCmpOps + Copy + Clone + Bounded,
+    N: Numeric + NumericOps + NumericCmpOps + Clone + MaybeBounded,
 {
     let mut left = match bounds.start_bound() {
-        Bound::Included(&s) => s,
-        Bound::Excluded(&s) => s + N::ONE,
-        Bound::Unbounded => N::MIN,
+        Bound::Included(s) => s.clone(),
+        Bound::Excluded(s) => s.clone() + N::ONE,
+        Bound::Unbounded => N::min_value().expect("The type has no lower bound"),
     };
     let mut right = match bounds.end_bound() {
-        Bound::Included(&e) => e + N::ONE,
-        Bound::Excluded(&e) => e,
-        Bound::Unbounded => N::MAX,
+        Bound::Included(e) => e.clone() + N::ONE,
+        Bound::Excluded(e) => e.clone(),
+        Bound::Unbounded => N::max_value().expect("The type has no upper bound"),
     };
 
     while left < right {
-        let mid = (left + right) / (N::ONE + N::ONE);
-        if f(mid) {
+        let mid = (left.clone() + right.clone()) / (N::ONE + N::ONE);
+        if f(mid.clone()) {
             right = mid;
         } else {
             left = mid + N::ONE;
@@ -1,14 +1,26 @@
 use std::ops::{Add, AddAssign, BitXor, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
+use crate::num::{NumericAssOps, NumericOps, Zero};
 use crate::utils::F64;
 
+/// A point or a vector in the plane.
+///
+/// Coordinates default to [`F64`], and can be any numeric type
+/// such as [`Rational`](crate::num::Rational) for exact arithmetic.
+/// Methods needing square roots or trigonometry are only available for [`F64`].
 #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
-pub struct Point {
-    pub x: F64,
-    pub y: F64,
+pub struct Point<T = F64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Add for Point {
+impl<T> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: NumericOps> Add for Point<T> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -19,7 +31,7 @@ impl Add for Point {
     }
 }
 
-impl Sub for Point {
+impl<T: NumericOps> Sub for Point<T> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -30,73 +42,85 @@ impl Sub for Point {
     }
 }
 
-impl Mul<F64> for Point {
-    type Output = Point;
+impl<T: NumericOps + Clone> Mul<T> for Point<T> {
+    type Output = Self;
 
-    fn mul(self, rhs: F64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Self {
-            x: self.x.mul(rhs),
+            x: self.x.mul(rhs.clone()),
             y: self.y.mul(rhs),
         }
     }
 }
 
-impl Div<F64> for Point {
-    type Output = Point;
+impl<T: NumericOps + Clone> Div<T> for Point<T> {
+    type Output = Self;
 
-    fn div(self, rhs: F64) -> Self::Output {
+    fn div(self, rhs: T) -> Self::Output {
         Self {
-            x: self.x.div(rhs),
+            x: self.x.div(rhs.clone()),
             y: self.y.div(rhs),
         }
     }
 }
 
-impl Mul<Point> for Point {
-    type Output = F64;
+impl<T: NumericOps> Mul<Point<T>> for Point<T> {
+    type Output = T;
 
-    fn mul(self, rhs: Point) -> Self::Output {
+    fn mul(self, rhs: Point<T>) -> Self::Output {
         self.x * rhs.x + self.y * rhs.y
     }
 }
 
-impl BitXor<Point> for Point {
-    type Output = F64;
+impl<T: NumericOps> BitXor<Point<T>> for Point<T> {
+    type Output = T;
 
-    fn bitxor(self, rhs: Point) -> Self::Output {
+    fn bitxor(self, rhs: Point<T>) -> Self::Output {
         self.x * rhs.y - self.y * rhs.x
     }
 }
 
-impl AddAssign for Point {
+impl<T: NumericAssOps> AddAssign for Point<T> {
     fn add_assign(&mut self, rhs: Self) {
         self.x.add_assign(rhs.x);
         self.y.add_assign(rhs.y);
     }
 }
 
-impl SubAssign for Point {
+impl<T: NumericAssOps> SubAssign for Point<T> {
     fn sub_assign(&mut self, rhs: Self) {
         self.x.sub_assign(rhs.x);
         self.y.sub_assign(rhs.y);
     }
 }
 
-impl MulAssign<F64> for Point {
-    fn mul_assign(&mut self, rhs: F64) {
-        self.x.mul_assign(rhs);
+impl<T: NumericAssOps + Clone> MulAssign<T> for Point<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x.mul_assign(rhs.clone());
         self.y.mul_assign(rhs);
     }
 }
 
-impl DivAssign<F64> for Point {
-    fn div_assign(&mut self, rhs: F64) {
-        self.x.div_assign(rhs);
+impl<T: NumericAssOps + Clone> DivAssign<T> for Point<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.x.div_assign(rhs.clone());
         self.y.div_assign(rhs);
     }
 }
 
-type Vector = Point;
+type Vector<T = F64> = Point<T>;
+
+impl<T: NumericOps + Zero + PartialOrd + Clone> Vector<T> {
+    /// Returns the area^2 of the parallelogram spanned by two vectors.
+    pub fn area2(&self, other: &Self) -> T {
+        self.clone() ^ other.clone()
+    }
+
+    /// Judge whether the vector p is on the left of the vector self.
+    pub fn to_left_test(&self, p: &Vector<T>) -> bool {
+        self.clone() ^ p.clone() > T::ZERO
+    }
+}
 
 impl Vector {
     pub fn length(&self) -> F64 {
@@ -108,11 +132,6 @@ impl Vector {
         F64((*self * *other / self.length() / other.length()).0.acos())
     }
 
-    /// Returns the area^2 of the parallelogram spanned by two vectors.
-    pub fn area2(&self, other: &Self) -> F64 {
-        *self ^ *other
-    }
-
     /// Rotate the vector by rad radians counter-clockwise.
     pub fn rotate(&self, rad: F64) -> Self {
         Self {
@@ -129,33 +148,46 @@ impl Vector {
             y: self.x / len,
         }
     }
-
-    /// Judge whether the vector p is on the left of the vector self.
-    pub fn to_left_test(&self, p: &Vector) -> bool {
-        *self ^ *p > F64(0.0)
-    }
 }
 
-pub struct Line {
-    pub p1: Point,
-    pub p2: Point,
+pub struct Line<T = F64> {
+    pub p1: Point<T>,
+    pub p2: Point<T>,
 }
 
-impl Line {
-    pub fn new(p1: Point, p2: Point) -> Self {
+impl<T: NumericOps + Zero + PartialOrd + Clone> Line<T> {
+    pub fn new(p1: Point<T>, p2: Point<T>) -> Self {
         Self { p1, p2 }
     }
 
-    pub fn at(&self, t: F64) -> Point {
-        self.p1 + (self.p2 - self.p1) / (self.p2 - self.p1).length() * t
+    fn direction(&self) -> Vector<T> {
+        self.p2.clone() - self.p1.clone()
     }
 
-    pub fn on_line(&self, p: &Point) -> bool {
-        (*p - self.p1) ^ (self.p2 - self.p1) == F64(0.0)
+    pub fn on_line(&self, p: &Point<T>) -> bool {
+        (p.clone() - self.p1.clone()) ^ self.direction() == T::ZERO
     }
 
     pub fn intersects_with_line(&self, other: &Self) -> bool {
-        (self.p2 - self.p1) ^ (other.p2 - other.p1) != F64(0.0)
+        self.direction() ^ other.direction() != T::ZERO
+    }
+
+    /// Returns the intersection point of two lines, or `None` if they are parallel.
+    ///
+    /// Only uses field operations, so it is exact for exact coordinate types.
+    pub fn intersection(&self, other: &Self) -> Option<Point<T>> {
+        if !self.intersects_with_line(other) {
+            return None;
+        }
+        let t = (other.direction() ^ (self.p1.clone() - other.p1.clone()))
+            / (self.direction() ^ other.direction());
+        Some(self.p1.clone() + self.direction() * t)
+    }
+}
+
+impl Line {
+    pub fn at(&self, t: F64) -> Point {
+        self.p1 + (self.p2 - self.p1) / (self.p2 - self.p1).length() * t
     }
 
     pub fn get_intersection(&self, other: &Self) -> Option<Point> {
@@ -177,16 +209,16 @@ impl Line {
     }
 }
 
-type Segment = Line;
+type Segment<T = F64> = Line<T>;
 
-impl Segment {
+impl<T: NumericOps + Zero + PartialOrd + Clone> Segment<T> {
     /// Judge whether the point p is on the segment self.
-    pub fn on_segment(&self, p: &Point) -> bool {
-        self.on_line(p) && (*p - self.p1) * (*p - self.p2) <= F64(0.0)
+    pub fn on_segment(&self, p: &Point<T>) -> bool {
+        self.on_line(p) && (p.clone() - self.p1.clone()) * (p.clone() - self.p2.clone()) <= T::ZERO
     }
 
     /// Judge whether the point p is strictly on the segment self.
-    pub fn on_segment_strict(&self, p: &Point) -> bool {
-        self.on_line(p) && (*p - self.p1) * (*p - self.p2) < F64(0.0)
+    pub fn on_segment_strict(&self, p: &Point<T>) -> bool {
+        self.on_line(p) && (p.clone() - self.p1.clone()) * (p.clone() - self.p2.clone()) < T::ZERO
     }
 }
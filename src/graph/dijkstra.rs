@@ -23,15 +23,8 @@ use std::collections::BinaryHeap;
 ///     // Read the number of nodes, the number of edges, and the source node.
 ///     scanln!(n, m, s);
 ///     // Create a graph with n nodes and storing nothing in each node,
-///     // and a usize in each edge as weight.
-///     let mut graph = Graph::<(), usize>::new(n);
-///     for _ in 0..m {
-///         // Read an edge u->v with weight w.
-///         let (u, v, w): (usize, usize, usize);
-///         scanln!(u, v, w);
-///         // Create an edge u->v with weight w.
-///         graph.add_edge(u, v, w);
-///     }
+///     // and a usize in each edge as weight, then read m edges u->v with weight w.
+///     let graph = Graph::<(), usize>::read_edges(n, m, std::io::stdin().lock());
 ///     let dist = dijkstra(s, &graph);
 ///     println!("{:?}", dist);
 /// }
@@ -84,12 +77,7 @@ mod tests {
         ));
         let (n, m, s): (usize, usize, usize);
         fscanln!(reader, n, m, s);
-        let mut graph = Graph::<(), usize>::new(n);
-        for _ in 0..m {
-            let (u, v, w): (usize, usize, usize);
-            fscanln!(reader, u, v, w);
-            graph.add_edge(u, v, w);
-        }
+        let graph = Graph::<(), usize>::read_edges(n, m, &mut reader);
         let dist = dijkstra(s, &graph);
         assert_eq!(
             dist[1..],
@@ -0,0 +1,197 @@
+use crate::data_structure::disjoint_set_union::RollbackDisjointSetUnion;
+use std::collections::HashMap;
+
+/// An operation on an undirected graph for [`dynamic_connectivity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectivityQuery {
+    /// Adds an edge between the two vertices. Parallel edges are allowed.
+    Add(usize, usize),
+    /// Removes one of the edges between the two vertices, which must exist.
+    Remove(usize, usize),
+    /// Asks whether the two vertices are connected.
+    Connected(usize, usize),
+}
+
+/// Offline Dynamic Connectivity - Answering connectivity queries while edges are added and removed
+///
+/// Every edge is alive during an interval of the operations, which is split into _O(log m)_ nodes
+/// of a segment tree over time. A depth-first traversal of the segment tree then unions the edges
+/// of each node in a [`RollbackDisjointSetUnion`] and rolls them back when leaving the node.
+/// - Input:
+///     - `n` - the number of vertices, numbered from 1
+///     - `queries` - the operations in order
+/// - Output:
+///     - The answers of the [`Connected`](ConnectivityQuery::Connected) queries in order
+///
+/// Complexity: _O(m log m log n)_.
+///
+/// # Examples
+///
+/// ```
+/// use cplit::graph::dynamic_connectivity::{dynamic_connectivity, ConnectivityQuery::*};
+///
+/// let queries = [
+///     Add(1, 2),
+///     Add(2, 3),
+///     Connected(1, 3),
+///     Remove(1, 2),
+///     Connected(1, 3),
+///     Add(3, 1),
+///     Connected(2, 1),
+/// ];
+/// assert_eq!(dynamic_connectivity(3, &queries), vec![true, false, true]);
+/// ```
+pub fn dynamic_connectivity(n: usize, queries: &[ConnectivityQuery]) -> Vec<bool> {
+    let m = queries.len();
+    if m == 0 {
+        return vec![];
+    }
+    let mut tree = vec![vec![]; m << 2];
+    // The times at which the alive copies of each edge were added.
+    let mut alive: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (i, &query) in queries.iter().enumerate() {
+        match query {
+            ConnectivityQuery::Add(u, v) => alive.entry((u.min(v), u.max(v))).or_default().push(i),
+            ConnectivityQuery::Remove(u, v) => {
+                let edge = (u.min(v), u.max(v));
+                match alive.get_mut(&edge).and_then(|starts| starts.pop()) {
+                    Some(start) => insert(&mut tree, 1, 0, m - 1, start, i - 1, edge),
+                    None => panic!("Removing an edge that does not exist: ({}, {})", u, v),
+                }
+            }
+            ConnectivityQuery::Connected(_, _) => {}
+        }
+    }
+    for (edge, starts) in alive {
+        for start in starts {
+            insert(&mut tree, 1, 0, m - 1, start, m - 1, edge);
+        }
+    }
+
+    let mut dsu = RollbackDisjointSetUnion::with_len(n);
+    let mut ans = vec![];
+    solve(&tree, 1, 0, m - 1, queries, &mut dsu, &mut ans);
+    ans
+}
+
+/// Adds `edge` to the nodes covering the times `ql..=qr`.
+fn insert(
+    tree: &mut [Vec<(usize, usize)>],
+    x: usize,
+    l: usize,
+    r: usize,
+    ql: usize,
+    qr: usize,
+    edge: (usize, usize),
+) {
+    if ql > qr {
+        return;
+    }
+    if ql <= l && r <= qr {
+        tree[x].push(edge);
+        return;
+    }
+    let m = (l + r) >> 1;
+    if ql <= m {
+        insert(tree, x << 1, l, m, ql, qr, edge);
+    }
+    if m < qr {
+        insert(tree, x << 1 | 1, m + 1, r, ql, qr, edge);
+    }
+}
+
+fn solve(
+    tree: &[Vec<(usize, usize)>],
+    x: usize,
+    l: usize,
+    r: usize,
+    queries: &[ConnectivityQuery],
+    dsu: &mut RollbackDisjointSetUnion,
+    ans: &mut Vec<bool>,
+) {
+    let snapshot = dsu.snapshot();
+    for &(u, v) in &tree[x] {
+        dsu.union(u, v);
+    }
+    if l == r {
+        if let ConnectivityQuery::Connected(u, v) = queries[l] {
+            ans.push(dsu.same(u, v));
+        }
+    } else {
+        let m = (l + r) >> 1;
+        solve(tree, x << 1, l, m, queries, dsu, ans);
+        solve(tree, x << 1 | 1, m + 1, r, queries, dsu, ans);
+    }
+    dsu.rollback(snapshot);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fscanln;
+    use crate::graph::dynamic_connectivity::{dynamic_connectivity, ConnectivityQuery};
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn loj_121() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+200 5
+2 123 127
+0 123 127
+2 123 127
+1 127 123
+2 123 127
+"#,
+        ));
+
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let mut queries = vec![];
+        for _ in 0..m {
+            let (op, x, y): (usize, usize, usize);
+            fscanln!(reader, op, x, y);
+            queries.push(match op {
+                0 => ConnectivityQuery::Add(x, y),
+                1 => ConnectivityQuery::Remove(x, y),
+                _ => ConnectivityQuery::Connected(x, y),
+            });
+        }
+        let ans: Vec<_> = dynamic_connectivity(n, &queries)
+            .into_iter()
+            .map(|connected| if connected { 'Y' } else { 'N' })
+            .collect();
+        assert_eq!(ans, vec!['N', 'Y', 'N']);
+    }
+
+    #[test]
+    fn parallel_edges_and_cycles() {
+        use ConnectivityQuery::*;
+        let queries = [
+            Add(1, 2),
+            Add(2, 1),
+            Remove(1, 2),
+            Connected(1, 2),
+            Remove(2, 1),
+            Connected(1, 2),
+            Add(2, 3),
+            Add(3, 4),
+            Add(4, 2),
+            Remove(3, 4),
+            Connected(3, 4),
+            Remove(2, 3),
+            Connected(3, 4),
+            Connected(2, 4),
+        ];
+        assert_eq!(
+            dynamic_connectivity(4, &queries),
+            vec![true, false, true, false, true]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Removing an edge that does not exist: (2, 1)")]
+    fn remove_missing_edge() {
+        use ConnectivityQuery::*;
+        dynamic_connectivity(2, &[Add(1, 2), Remove(1, 2), Remove(2, 1)]);
+    }
+}
@@ -141,8 +141,7 @@ mod tests {
         for _ in 0..m {
             let (u, v): (usize, usize);
             fscanln!(reader, u, v);
-            graph.add_edge(u, v, false);
-            graph.add_edge(v, u, false);
+            graph.add_undirected_edge(u, v, false);
             graph.nodes[v] += 1;
             graph.nodes[u] += 1;
         }
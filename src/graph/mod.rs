@@ -5,8 +5,10 @@
 //! All graph algorithms are implemented for the following graph representation: [`Graph`],
 //! which is a simple graph representation using adjacency list.
 
+use crate::io::{Readable, Scanner};
 use std::cmp::max;
 use std::cmp::Ordering::{self, Less};
+use std::io::BufRead;
 use std::iter::from_fn;
 /// Graph representation using adjacency list.
 ///
@@ -58,6 +60,42 @@ where
         Self::new(0)
     }
 
+    /// Create a new graph with `n` nodes and `m` directed edges read from `reader`.
+    ///
+    /// Each edge is given as `from to info`, where `info` is read through [`Readable`].
+    /// For unweighted graphs, use `E = ()` which reads nothing.
+    pub fn read_edges<R>(n: usize, m: usize, reader: R) -> Self
+    where
+        R: BufRead,
+        E: Readable,
+    {
+        let mut graph = Self::new(n);
+        let mut scanner = Scanner::new(reader);
+        for _ in 0..m {
+            let (from, to, info) = scanner.token();
+            graph.add_edge(from, to, info);
+        }
+        graph
+    }
+
+    /// Create a new graph with `n` nodes and `m` undirected edges read from `reader`.
+    ///
+    /// Each edge is given as `from to info` and added by [`Graph::add_undirected_edge`].
+    /// For unweighted graphs, use `E = ()` which reads nothing.
+    pub fn read_undirected_edges<R>(n: usize, m: usize, reader: R) -> Self
+    where
+        R: BufRead,
+        E: Readable,
+    {
+        let mut graph = Self::new(n);
+        let mut scanner = Scanner::new(reader);
+        for _ in 0..m {
+            let (from, to, info) = scanner.token();
+            graph.add_undirected_edge(from, to, info);
+        }
+        graph
+    }
+
     /// Get the number of nodes in the graph.
     pub fn len_nodes(&self) -> usize {
         self.nodes.len() - 1
@@ -89,7 +127,7 @@ where
         (self.edges[p2].0, p2) = (0, self.edges[p2].0);
 
         p1 = self.sort_edges_inner(p1, len / 2, is_less);
-        p2 = self.sort_edges_inner(p2, (len + 1) / 2, is_less);
+        p2 = self.sort_edges_inner(p2, len.div_ceil(2), is_less);
         let mut lst;
         if is_less(
             &(self.edges[p1].1, &self.nodes[self.edges[p1].1]),
@@ -138,12 +176,16 @@ where
             self.sort_edges_inner(self.head[node], len, &mut |a, b| compare(a, b) == Less);
     }
 
-    /// Add an undirected edge between `from` and `to` with information `info`.
-    pub fn add_edge(&mut self, from: usize, to: usize, info: E) {
+    fn reserve_nodes(&mut self, from: usize, to: usize) {
         if max(from, to) >= self.nodes.len() {
             self.nodes.resize(max(from, to) + 1, V::default());
             self.head.resize(max(from, to) + 1, 0);
         }
+    }
+
+    /// Add a directed edge from `from` to `to` with information `info`.
+    pub fn add_edge(&mut self, from: usize, to: usize, info: E) {
+        self.reserve_nodes(from, to);
         if self.erased.is_empty() {
             self.edges.push((self.head[from], to, info));
             self.head[from] = self.edges.len() - 1;
@@ -154,6 +196,24 @@ where
         }
     }
 
+    /// Add an undirected edge between `from` and `to` with information `info`.
+    ///
+    /// The edge is stored as two directed edges with indices `2k - 1` and `2k`,
+    /// so that [`TWIN`] maps each of them to the other.
+    /// Erased slots are never reused here, as they would break the pairing.
+    pub fn add_undirected_edge(&mut self, from: usize, to: usize, info: E) {
+        self.reserve_nodes(from, to);
+        if self.edges.len() % 2 == 0 {
+            // Skip an even slot, leaving it erased for later directed edges.
+            self.edges.push(Default::default());
+            self.erased.push(self.edges.len() - 1);
+        }
+        self.edges.push((self.head[from], to, info.clone()));
+        self.head[from] = self.edges.len() - 1;
+        self.edges.push((self.head[to], from, info));
+        self.head[to] = self.edges.len() - 1;
+    }
+
     /// Returns an iterator over the edges from the edge with index `edge`.
     /// The iterator returns the destination node, and the information stored in the edge.
     pub fn get_edges_from(&self, mut edge: usize) -> impl Iterator<Item = (&usize, &E)> {
@@ -202,7 +262,7 @@ where
     pub fn get_edges_from_once<'a>(
         &'a self,
         cur: &'a mut usize,
-    ) -> impl Iterator<Item = (&usize, &E)> + 'a {
+    ) -> impl Iterator<Item = (&'a usize, &'a E)> + 'a {
         from_fn(move || {
             if *cur == 0 {
                 return None;
@@ -219,7 +279,7 @@ where
     pub fn get_edges_enum_from_once<'a>(
         &'a self,
         cur: &'a mut usize,
-    ) -> impl Iterator<Item = (usize, (&usize, &E))> + 'a {
+    ) -> impl Iterator<Item = (usize, (&'a usize, &'a E))> + 'a {
         from_fn(move || {
             if *cur == 0 {
                 return None;
@@ -262,12 +322,15 @@ pub const TWIN: fn(usize) -> usize = |idx| ((idx - 1) ^ 1) + 1;
 pub mod degree;
 pub mod dijkstra;
 pub mod distance;
+pub mod dynamic_connectivity;
 pub mod hierholzer;
 pub mod scc;
 
 #[doc(inline)]
 pub use self::dijkstra::dijkstra;
 #[doc(inline)]
+pub use self::dynamic_connectivity::{dynamic_connectivity, ConnectivityQuery};
+#[doc(inline)]
 pub use self::hierholzer::{hierholzer_directed, hierholzer_undirected};
 #[doc(inline)]
 pub use self::scc::scc;
@@ -276,3 +339,39 @@ pub use self::scc::scc;
 pub use self::degree::Degree;
 #[doc(inline)]
 pub use self::distance::Distance;
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, TWIN};
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn twin_edges() {
+        let reader = BufReader::new(Cursor::new(
+            r#"
+1 2 5
+2 3 7
+3 1 2
+"#,
+        ));
+        let mut graph = Graph::<(), i64>::read_undirected_edges(4, 3, reader);
+        graph.add_edge(4, 1, 9);
+        // Edge 8 is skipped to keep the pair at 9 and 10, and reused by the next directed edge.
+        graph.add_undirected_edge(3, 4, 1);
+        graph.add_edge(2, 4, 6);
+        assert_eq!(graph.len_edges(), 10);
+        assert_eq!(graph.get_edge(7).1, 1);
+        assert_eq!(graph.get_edge(8).1, 4);
+
+        for u in 1..=graph.len_nodes() {
+            for (i, (&v, &w)) in graph.get_edges_enum(u) {
+                if i == 7 || i == 8 {
+                    continue;
+                }
+                let (_, to, &info) = graph.get_twin_edge(i);
+                assert_eq!((to, info), (u, w));
+                assert!(graph.get_edges_enum(v).any(|(j, _)| j == TWIN(i)));
+            }
+        }
+    }
+}
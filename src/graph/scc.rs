@@ -1,5 +1,6 @@
 use crate::graph::Graph;
 
+#[allow(clippy::too_many_arguments)]
 fn tarjan<V, E>(
     graph: &Graph<V, E>,
     u: usize,
@@ -0,0 +1,18 @@
+//! Input and Output
+//!
+//! This module contains the buffered I/O helpers behind the scanning and printing macros.
+//!
+//! For now, the following types are available:
+//!
+//! [`Scanner`], [`Readable`], [`Writer`]
+
+pub mod readable;
+pub mod scanner;
+pub mod writer;
+
+#[doc(inline)]
+pub use self::readable::Readable;
+#[doc(inline)]
+pub use self::scanner::Scanner;
+#[doc(inline)]
+pub use self::writer::Writer;
@@ -0,0 +1,149 @@
+use crate::io::Scanner;
+use std::io::BufRead;
+
+/// Types which can be read by a [`Scanner`].
+///
+/// Implemented for primitives and [`String`] (one token each), `()` (nothing at all),
+/// `char` (one non-whitespace character, so that grids like `#.#` can be read cell by cell),
+/// tuples and fixed-size arrays of readable types (read element by element),
+/// and `Vec<u8>`/`Vec<char>` (one token as a 1-based string, index 0 left as default).
+///
+/// For a user-defined type implementing [`FromStr`](std::str::FromStr),
+/// [`Scanner::parse`] does the job:
+/// ```
+/// use cplit::io::{Readable, Scanner};
+/// use std::io::BufRead;
+///
+/// struct Id(usize);
+///
+/// impl Readable for Id {
+///     fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+///         Id(scanner.parse())
+///     }
+/// }
+/// ```
+pub trait Readable: Sized {
+    /// Reads a value, skipping the leading whitespaces.
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self;
+}
+
+macro_rules! readable_parse_impl {
+    ($name:ident for $($t:ty)*) => ($(
+        impl $name for $t {
+            fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+                scanner.parse()
+            }
+        }
+    )*)
+}
+
+readable_parse_impl!(Readable for usize u8 u16 u32 u64 u128);
+readable_parse_impl!(Readable for isize i8 i16 i32 i64 i128);
+readable_parse_impl!(Readable for f32 f64 bool String);
+
+impl Readable for () {
+    fn read<R: BufRead>(_: &mut Scanner<R>) -> Self {}
+}
+
+impl Readable for char {
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+        scanner.expect_token();
+        scanner.read_char()
+    }
+}
+
+impl Readable for Vec<u8> {
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+        scanner.expect_token();
+        std::iter::once(0)
+            .chain(scanner.read_bytes().iter().copied())
+            .collect()
+    }
+}
+
+impl Readable for Vec<char> {
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+        scanner.expect_token();
+        std::iter::once('\0')
+            .chain(String::from_utf8_lossy(scanner.read_bytes()).chars())
+            .collect()
+    }
+}
+
+impl<T, const N: usize> Readable for [T; N]
+where
+    T: Readable,
+{
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+        std::array::from_fn(|_| T::read(scanner))
+    }
+}
+
+macro_rules! tuple_readable_impl {
+    ( $( $name:ident )+ ) => {
+        impl<$($name: Readable),+> Readable for ($($name,)+)
+        {
+            fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+                ($($name::read(scanner),)+)
+            }
+        }
+    };
+}
+
+tuple_readable_impl!(A);
+tuple_readable_impl!(A B);
+tuple_readable_impl!(A B C);
+tuple_readable_impl!(A B C D);
+tuple_readable_impl!(A B C D E);
+tuple_readable_impl!(A B C D E F);
+
+#[cfg(test)]
+mod tests {
+    use crate::fscanln;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn tuples_and_grids() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+3 2
+1 2 -5
+2 3 7
+#.#
+. # .
+abc
+de
+1 2 3 4
+5 6 7 8
+"#,
+        ));
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let edges: Vec<(usize, usize, i64)>;
+        fscanln!(reader, edges; m);
+        assert_eq!(edges, vec![(0, 0, 0), (1, 2, -5), (2, 3, 7)]);
+
+        let grid: Vec<Vec<char>>;
+        fscanln!(reader, grid; 2, n);
+        assert_eq!(
+            grid,
+            vec![
+                vec!['\0'; 4],
+                vec!['\0', '#', '.', '#'],
+                vec!['\0', '.', '#', '.'],
+            ]
+        );
+
+        let words: Vec<Vec<u8>>;
+        fscanln!(reader, words; 2);
+        assert_eq!(words, vec![vec![], b"\0abc".to_vec(), b"\0de".to_vec()]);
+
+        let (a, b): ([u32; 3], (u8, [i8; 1]));
+        fscanln!(reader, a, b);
+        assert_eq!((a, b), ([1, 2, 3], (4, [5])));
+
+        let g: Vec<Vec<u64>>;
+        fscanln!(reader, g; 1, 3);
+        assert_eq!(g, vec![vec![0; 4], vec![0, 6, 7, 8]]);
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt::Debug;
+use std::io::{BufRead, Read, StdinLock};
+use std::str::{from_utf8, FromStr};
+
+use crate::io::Readable;
+
+/// Buffered token scanner over any [`BufRead`].
+///
+/// Tokens are taken straight from the buffer of the underlying reader,
+/// so nothing beyond the current token is ever consumed.
+/// The leftover tokens of a line therefore stay in the reader and can be picked up by the next call,
+/// even if the scanner is dropped and a new one is created over the same reader.
+///
+/// [`scanln!`](crate::scanln) and [`fscanln!`](crate::fscanln) are routed through this type.
+///
+/// # Examples
+/// ```no_run
+/// use cplit::io::Scanner;
+///
+/// fn main() {
+///     let mut sc = Scanner::stdin();
+///     let n: usize = sc.token();
+///     let s: i64 = (0..n).map(|_| sc.token::<i64>()).sum();
+///     println!("{}", s);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Scanner<R>
+where
+    R: BufRead,
+{
+    reader: R,
+    // Scratch space for tokens, reused between calls.
+    buf: Vec<u8>,
+}
+
+impl Scanner<StdinLock<'static>> {
+    /// Constructs a scanner over the locked standard input.
+    pub fn stdin() -> Self {
+        Self::new(std::io::stdin().lock())
+    }
+}
+
+impl Default for Scanner<StdinLock<'static>> {
+    fn default() -> Self {
+        Self::stdin()
+    }
+}
+
+impl<R> Scanner<R>
+where
+    R: BufRead,
+{
+    /// Constructs a new scanner over `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Unwraps the scanner, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Peeks the next byte without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        self.reader.fill_buf().unwrap().first().copied()
+    }
+
+    /// Skips bytes while `f` holds, returns the first byte that fails `f` (not consumed).
+    fn skip_while(&mut self, f: impl Fn(u8) -> bool) -> Option<u8> {
+        loop {
+            let buf = self.reader.fill_buf().unwrap();
+            if buf.is_empty() {
+                return None;
+            }
+            match buf.iter().position(|&c| !f(c)) {
+                Some(i) => {
+                    let c = buf[i];
+                    self.reader.consume(i);
+                    return Some(c);
+                }
+                None => {
+                    let len = buf.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+
+    /// Skips all whitespaces including line breaks.
+    /// Returns `false` if the end of input is reached.
+    pub fn skip_whitespace(&mut self) -> bool {
+        self.skip_while(|c| c.is_ascii_whitespace()).is_some()
+    }
+
+    /// Skips whitespaces on the current line.
+    /// Returns `false` if the end of the line (or input) is reached.
+    fn skip_inline_whitespace(&mut self) -> bool {
+        !matches!(
+            self.skip_while(|c| c != b'\n' && c.is_ascii_whitespace()),
+            None | Some(b'\n')
+        )
+    }
+
+    /// Moves to the next line if nothing but whitespaces is left on the current line.
+    pub fn skip_empty_tail(&mut self) {
+        if !self.skip_inline_whitespace() && self.peek() == Some(b'\n') {
+            self.reader.consume(1);
+        }
+    }
+
+    /// Reads the raw bytes of the next token, which starts at the current position.
+    pub(crate) fn read_bytes(&mut self) -> &[u8] {
+        self.buf.clear();
+        loop {
+            let buf = self.reader.fill_buf().unwrap();
+            if buf.is_empty() {
+                break;
+            }
+            match buf.iter().position(|c| c.is_ascii_whitespace()) {
+                Some(i) => {
+                    self.buf.extend_from_slice(&buf[..i]);
+                    self.reader.consume(i);
+                    break;
+                }
+                None => {
+                    let len = buf.len();
+                    self.buf.extend_from_slice(buf);
+                    self.reader.consume(len);
+                }
+            }
+        }
+        &self.buf
+    }
+
+    /// Reads a single (UTF-8) character, which starts at the current position.
+    pub(crate) fn read_char(&mut self) -> char {
+        self.buf.clear();
+        let first = self.peek().unwrap();
+        let len = match first.leading_ones() {
+            0 => 1,
+            n => n as usize,
+        };
+        for _ in 0..len {
+            let c = self.peek().unwrap();
+            self.buf.push(c);
+            self.reader.consume(1);
+        }
+        from_utf8(&self.buf).unwrap().chars().next().unwrap()
+    }
+
+    /// Skips to the start of the next token.
+    ///
+    /// Panics if the end of input is reached.
+    pub(crate) fn expect_token(&mut self) {
+        if !self.skip_whitespace() {
+            panic!("Unexpected end of input");
+        }
+    }
+
+    /// Reads the next token with [`FromStr`], possibly from the following lines.
+    ///
+    /// This is handy to implement [`Readable`] for user-defined types.
+    ///
+    /// Panics if the end of input is reached.
+    pub fn parse<T>(&mut self) -> T
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        self.expect_token();
+        from_utf8(self.read_bytes()).unwrap().parse().unwrap()
+    }
+
+    /// Reads the next value, possibly from the following lines.
+    ///
+    /// Panics if the end of input is reached.
+    pub fn token<T>(&mut self) -> T
+    where
+        T: Readable,
+    {
+        T::read(self)
+    }
+
+    /// Reads the next value starting on the current line.
+    ///
+    /// Returns `None` without consuming the line break if the end of the line is reached.
+    pub fn line_token<T>(&mut self) -> Option<T>
+    where
+        T: Readable,
+    {
+        if self.skip_inline_whitespace() {
+            Some(T::read(self))
+        } else {
+            None
+        }
+    }
+
+    /// Reads `n` values into a 1-based vector, leaving index 0 as default.
+    pub fn vec<T>(&mut self, n: usize) -> Vec<T>
+    where
+        T: Readable + Default,
+    {
+        std::iter::once(T::default())
+            .chain((0..n).map(|_| self.token()))
+            .collect()
+    }
+
+    /// Reads `n` rows of `m` values into a 1-based grid,
+    /// leaving row 0 and column 0 as default.
+    ///
+    /// With `T = char`, both `#.#` and `# . #` rows are accepted.
+    pub fn grid<T>(&mut self, n: usize, m: usize) -> Vec<Vec<T>>
+    where
+        T: Readable + Default + Clone,
+    {
+        std::iter::once(vec![T::default(); m + 1])
+            .chain((0..n).map(|_| self.vec(m)))
+            .collect()
+    }
+
+    /// Reads the remaining values of a line into a 1-based vector, leaving index 0 as default.
+    ///
+    /// If nothing is left on the current line, the next line is read instead.
+    pub fn line<T>(&mut self) -> Vec<T>
+    where
+        T: Readable + Default,
+    {
+        self.skip_empty_tail();
+        std::iter::once(T::default())
+            .chain(std::iter::from_fn(|| self.line_token()))
+            .collect()
+    }
+}
+
+impl<R> Read for Scanner<R>
+where
+    R: BufRead,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl<R> BufRead for Scanner<R>
+where
+    R: BufRead,
+{
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.reader.consume(amt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fscanln;
+    use crate::io::Scanner;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn leftover_tokens() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+3 -4 hello
+1 2 3
+4 5
+
+6
+"#,
+        ));
+        let (a, b, s): (usize, i64, String);
+        fscanln!(reader, a);
+        fscanln!(reader, b);
+        fscanln!(reader, s);
+        assert_eq!((a, b, s.as_str()), (3, -4, "hello"));
+
+        let v: Vec<u32>;
+        fscanln!(reader, v;);
+        assert_eq!(v, vec![0, 1, 2, 3]);
+
+        let (x, y, z): (u8, u8, u8);
+        fscanln!(reader, x, y, z; ?);
+        assert_eq!((x, y, z), (4, 5, 0));
+
+        let mut sc = Scanner::new(reader);
+        assert_eq!(sc.token::<i32>(), 6);
+        assert_eq!(sc.line_token::<i32>(), None);
+        assert!(!sc.skip_whitespace());
+    }
+
+    #[test]
+    fn small_buffer() {
+        let reader = BufReader::with_capacity(2, Cursor::new("12345 678\n-9"));
+        let mut sc = Scanner::new(reader);
+        assert_eq!(sc.vec::<i64>(3), vec![0, 12345, 678, -9]);
+    }
+}
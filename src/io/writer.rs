@@ -0,0 +1,141 @@
+use std::fmt::Display;
+use std::io::{BufWriter, StdoutLock, Write};
+
+/// Buffered writer over any [`Write`], flushed on drop.
+///
+/// [`printv!`](crate::printv) and [`fprintln!`](crate::fprintln) accept it like any other [`Write`].
+///
+/// # Examples
+/// ```no_run
+/// use cplit::io::Writer;
+/// use cplit::{fprintln, scanln};
+///
+/// fn main() {
+///     let n: usize;
+///     scanln!(n);
+///     let v: Vec<i64>;
+///     scanln!(v; n);
+///     let mut out = Writer::stdout();
+///     fprintln!(out, n, v[1..].iter().sum::<i64>());
+///     fprintln!(out, v;);
+///     out.join(v.iter().rev(), ",");
+///     out.newline();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Writer<W>
+where
+    W: Write,
+{
+    inner: BufWriter<W>,
+}
+
+impl Writer<StdoutLock<'static>> {
+    /// Constructs a writer over the locked standard output.
+    pub fn stdout() -> Self {
+        Self::new(std::io::stdout().lock())
+    }
+}
+
+impl Default for Writer<StdoutLock<'static>> {
+    fn default() -> Self {
+        Self::stdout()
+    }
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Constructs a new writer over `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: BufWriter::new(inner),
+        }
+    }
+
+    /// Writes a single value.
+    pub fn put<T>(&mut self, value: T)
+    where
+        T: Display,
+    {
+        write!(self.inner, "{}", value).unwrap();
+    }
+
+    /// Writes a line break.
+    pub fn newline(&mut self) {
+        self.inner.write_all(b"\n").unwrap();
+    }
+
+    /// Writes the items of `iter` separated by `sep`.
+    pub fn join<I>(&mut self, iter: I, sep: &str)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        join(&mut self.inner, iter, sep);
+    }
+}
+
+impl<W> Write for Writer<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W> Drop for Writer<W>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        let _ = self.inner.flush();
+    }
+}
+
+/// Writes the items of `iter` into `writer` separated by `sep`.
+pub fn join<W, I>(writer: &mut W, iter: I, sep: &str)
+where
+    W: Write + ?Sized,
+    I: IntoIterator,
+    I::Item: Display,
+{
+    let mut iter = iter.into_iter();
+    if let Some(first) = iter.next() {
+        write!(writer, "{}", first).unwrap();
+        for item in iter {
+            write!(writer, "{}{}", sep, item).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fprintln;
+    use crate::io::Writer;
+
+    #[test]
+    fn flush_on_drop() {
+        let mut buf = Vec::new();
+        {
+            let mut out = Writer::new(&mut buf);
+            let v: Vec<usize> = vec![0, 3, 1, 2];
+            fprintln!(out, 1, "a", -2.5);
+            fprintln!(out, v;);
+            fprintln!(out, v; ", ");
+            fprintln!(out);
+            out.join(v.iter().skip(2), "-");
+            out.newline();
+        }
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "1 a -2.5\n3 1 2\n3, 1, 2\n\n1-2\n"
+        );
+    }
+}
@@ -32,6 +32,8 @@ pub mod data_structure;
 pub mod general;
 pub mod geometry;
 pub mod graph;
+pub mod io;
+pub mod linalg;
 pub mod num;
 pub mod number_theory;
 pub mod utils;
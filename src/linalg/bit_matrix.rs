@@ -0,0 +1,333 @@
+use crate::linalg::Solution;
+use std::fmt::{Debug, Display};
+
+/// Dense matrix over GF(2) with rows packed into `u64` words, 1-indexed.
+///
+/// Row 0 and column 0 are buffers as in [`Matrix`](crate::linalg::Matrix),
+/// and elimination works on whole words, for XOR systems and bases.
+///
+/// # Examples
+/// ```
+/// use cplit::linalg::BitMatrix;
+///
+/// // Lights Out on a path of 3 lamps: pressing a lamp toggles it and its neighbours.
+/// let a = BitMatrix::from(vec![
+///     vec![false; 4],
+///     vec![false, true, true, false],
+///     vec![false, true, true, true],
+///     vec![false, false, true, true],
+/// ]);
+/// let s = a.solve(&[false, true, true, true]).unwrap();
+/// assert_eq!(s.particular, [false, false, true, false]);
+/// assert_eq!(a.rank(), 3);
+/// assert!(a.det());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    /// Bit `j` of row `i` is stored in bit `j % 64` of `data[i][j / 64]`.
+    data: Vec<Vec<u64>>,
+}
+
+impl BitMatrix {
+    /// Constructs a `rows` by `cols` zero matrix.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![vec![0; cols / 64 + 1]; rows + 1],
+        }
+    }
+
+    /// Constructs the `n` by `n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::new(n, n);
+        for i in 1..=n {
+            m.set(i, i, true);
+        }
+        m
+    }
+
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn check(&self, i: usize, j: usize) {
+        if !(1..=self.rows).contains(&i) || !(1..=self.cols).contains(&j) {
+            panic!(
+                "Index out of bounds: the size is {}x{} but the index is ({}, {})",
+                self.rows, self.cols, i, j
+            );
+        }
+    }
+
+    /// The entry at row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        self.check(i, j);
+        self.data[i][j / 64] >> (j % 64) & 1 == 1
+    }
+
+    /// Sets the entry at row `i` and column `j` to `val`.
+    pub fn set(&mut self, i: usize, j: usize, val: bool) {
+        self.check(i, j);
+        if val {
+            self.data[i][j / 64] |= 1 << (j % 64);
+        } else {
+            self.data[i][j / 64] &= !(1 << (j % 64));
+        }
+    }
+
+    /// The transposed matrix.
+    pub fn transpose(&self) -> Self {
+        let mut res = Self::new(self.cols, self.rows);
+        for i in 1..=self.rows {
+            for j in 1..=self.cols {
+                if self.get(i, j) {
+                    res.set(j, i, true);
+                }
+            }
+        }
+        res
+    }
+
+    /// The rank.
+    ///
+    /// Complexity: _O(n m min(n, m) / 64)_.
+    pub fn rank(&self) -> usize {
+        eliminate(&mut self.data.clone(), self.cols).len()
+    }
+
+    /// The determinant of a square matrix, `true` if and only if it is invertible.
+    ///
+    /// Complexity: _O(n^3 / 64)_.
+    pub fn det(&self) -> bool {
+        if self.rows != self.cols {
+            panic!(
+                "Dimension mismatch: cannot take the determinant of a {}x{} matrix",
+                self.rows, self.cols
+            );
+        }
+        self.rank() == self.rows
+    }
+
+    /// The inverse by Gauss-Jordan elimination, or `None` if the matrix is singular.
+    ///
+    /// Complexity: _O(n^3 / 64)_.
+    pub fn inv(&self) -> Option<Self> {
+        let n = self.rows;
+        if n != self.cols {
+            panic!(
+                "Dimension mismatch: cannot invert a {}x{} matrix",
+                n, self.cols
+            );
+        }
+        let mut a = Self::new(n, 2 * n);
+        for i in 1..=n {
+            for j in 1..=n {
+                a.set(i, j, self.get(i, j));
+            }
+            a.set(i, n + i, true);
+        }
+        if eliminate(&mut a.data, n).len() < n {
+            return None;
+        }
+        let mut res = Self::new(n, n);
+        for i in 1..=n {
+            for j in 1..=n {
+                res.set(i, j, a.get(i, n + j));
+            }
+        }
+        Some(res)
+    }
+
+    /// Solves `self * x = b` for `b` padded with index 0,
+    /// or returns `None` if the system is inconsistent.
+    ///
+    /// Complexity: _O(n m min(n, m) / 64)_.
+    pub fn solve(&self, b: &[bool]) -> Option<Solution<bool>> {
+        let (n, m) = (self.rows, self.cols);
+        if b.len() != n + 1 {
+            panic!(
+                "Dimension mismatch: {}x{} and a vector of length {}",
+                n,
+                m,
+                b.len().saturating_sub(1)
+            );
+        }
+        let mut a = Self::new(n, m + 1);
+        for (i, &x) in b.iter().enumerate().skip(1) {
+            a.data[i][..self.data[i].len()].copy_from_slice(&self.data[i]);
+            a.set(i, m + 1, x);
+        }
+        let pivots = eliminate(&mut a.data, m);
+        if (pivots.len() + 1..=n).any(|i| a.get(i, m + 1)) {
+            return None;
+        }
+        let mut particular = vec![false; m + 1];
+        let mut is_pivot = vec![false; m + 1];
+        for (k, &j) in pivots.iter().enumerate() {
+            particular[j] = a.get(k + 1, m + 1);
+            is_pivot[j] = true;
+        }
+        let basis = (1..=m)
+            .filter(|&f| !is_pivot[f])
+            .map(|f| {
+                let mut v = vec![false; m + 1];
+                v[f] = true;
+                for (k, &j) in pivots.iter().enumerate() {
+                    v[j] = a.get(k + 1, f);
+                }
+                v
+            })
+            .collect();
+        Some(Solution { particular, basis })
+    }
+}
+
+/// Reduces the padded rows to reduced row echelon form over the columns `1..=cols`,
+/// carrying any further columns along, and returns the pivot column of each nonzero row.
+fn eliminate(a: &mut [Vec<u64>], cols: usize) -> Vec<usize> {
+    let n = a.len() - 1;
+    let mut pivots = Vec::new();
+    for j in 1..=cols {
+        let i = pivots.len() + 1;
+        if i > n {
+            break;
+        }
+        let (w, bit) = (j / 64, 1 << (j % 64));
+        let Some(p) = (i..=n).find(|&r| a[r][w] & bit != 0) else {
+            continue;
+        };
+        a.swap(i, p);
+        let pivot = a[i].clone();
+        for (r, row) in a.iter_mut().enumerate().skip(1) {
+            if r != i && row[w] & bit != 0 {
+                for (x, y) in row.iter_mut().zip(&pivot).skip(w) {
+                    *x ^= y;
+                }
+            }
+        }
+        pivots.push(j);
+    }
+    pivots
+}
+
+impl From<Vec<Vec<bool>>> for BitMatrix {
+    /// Constructs a matrix from rows padded with row 0 and column 0.
+    fn from(data: Vec<Vec<bool>>) -> Self {
+        if data.is_empty() || data.iter().any(|row| row.len() != data[0].len()) {
+            panic!("The rows of a matrix must be non-empty and of the same length");
+        }
+        let mut res = Self::new(data.len() - 1, data[0].len() - 1);
+        for (i, row) in data.iter().enumerate().skip(1) {
+            for (j, &x) in row.iter().enumerate().skip(1) {
+                res.set(i, j, x);
+            }
+        }
+        res
+    }
+}
+
+impl Display for BitMatrix {
+    /// Prints the rows on separate lines as strings of `0` and `1`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in 1..=self.rows {
+            if i > 1 {
+                writeln!(f)?;
+            }
+            for j in 1..=self.cols {
+                write!(f, "{}", self.get(i, j) as u8)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Debug for BitMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries((1..=self.rows).map(|i| {
+                (1..=self.cols)
+                    .map(|j| if self.get(i, j) { '1' } else { '0' })
+                    .collect::<String>()
+            }))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fscanln;
+    use crate::linalg::BitMatrix;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p2447() {
+        // Each line is a subset of coins, and whether their total weight is odd.
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+3 5
+011 1
+110 1
+101 0
+111 1
+010 1
+"#,
+        ));
+        let (n, m): (usize, usize);
+        fscanln!(reader, n, m);
+        let mut a = BitMatrix::new(m, n);
+        let mut b = vec![false; m + 1];
+        for (i, x) in b.iter_mut().enumerate().skip(1) {
+            let (s, r): (Vec<u8>, u8);
+            fscanln!(reader, s, r);
+            for (j, &c) in s.iter().enumerate().skip(1) {
+                a.set(i, j, c == b'1');
+            }
+            *x = r == 1;
+        }
+        let s = a.solve(&b).unwrap();
+        assert_eq!(s.dim(), 0);
+        assert_eq!(s.particular, [false, false, true, false]);
+        b[4] = false;
+        assert!(a.solve(&b).is_none());
+    }
+
+    #[test]
+    fn wide() {
+        // A 100x100 upper bidiagonal matrix is invertible, with an upper triangular inverse of ones.
+        let n = 100;
+        let mut a = BitMatrix::new(n, n);
+        for i in 1..=n {
+            a.set(i, i, true);
+            if i < n {
+                a.set(i, i + 1, true);
+            }
+        }
+        let inv = a.inv().unwrap();
+        for i in 1..=n {
+            for j in 1..=n {
+                assert_eq!(inv.get(i, j), j >= i);
+            }
+        }
+        assert_eq!(a.inv().unwrap().inv().unwrap(), a);
+        assert_eq!(a.transpose().rank(), n);
+
+        a.set(n, n - 1, true);
+        assert_eq!(a.rank(), n - 1);
+        assert!(!a.det());
+        assert!(a.inv().is_none());
+        let mut b = vec![false; n + 1];
+        let s = a.solve(&b).unwrap();
+        assert_eq!(s.dim(), 1);
+        b[n] = true;
+        assert!(a.solve(&b).is_none());
+    }
+}
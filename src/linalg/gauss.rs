@@ -0,0 +1,346 @@
+use crate::linalg::{Matrix, PlusTimes};
+use crate::num::{DynModInt, ModInt, Numeric, NumericOps, Rational};
+use crate::utils::F64;
+
+/// The choice of pivots in Gaussian elimination.
+///
+/// By default the first nonzero entry of a column is taken, which is exact for fields such as
+/// [`ModInt`] and [`Rational`].
+/// Floating-point types take the entry of largest absolute value instead (partial pivoting),
+/// since a tiny pivot would blow up the rounding errors.
+pub trait Pivot {
+    /// Returns `true` if `self` is a strictly better pivot than `other`.
+    fn better_pivot(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+impl<const M: u64> Pivot for ModInt<M> {}
+impl Pivot for DynModInt {}
+impl<T> Pivot for Rational<T> {}
+
+impl Pivot for F64 {
+    fn better_pivot(&self, other: &Self) -> bool {
+        self.0.abs() > other.0.abs()
+    }
+}
+
+macro_rules! float_pivot_impl {
+    ($($t:ty)*) => ($(
+        impl Pivot for $t {
+            fn better_pivot(&self, other: &Self) -> bool {
+                self.abs() > other.abs()
+            }
+        }
+    )*)
+}
+
+float_pivot_impl!(f32 f64);
+
+/// The solution space of a linear system `A x = b`.
+///
+/// Every solution is `particular` plus a linear combination of `basis`.
+/// Vectors are padded with index 0.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution<T> {
+    /// One solution of the system, with the free variables set to zero.
+    pub particular: Vec<T>,
+    /// A basis of the null space of `A`, one vector per free variable.
+    pub basis: Vec<Vec<T>>,
+}
+
+impl<T> Solution<T> {
+    /// The dimension of the solution space, `0` if the solution is unique.
+    pub fn dim(&self) -> usize {
+        self.basis.len()
+    }
+}
+
+/// Reduces the padded rows to reduced row echelon form over the columns `1..=cols`,
+/// carrying any further columns along.
+///
+/// Returns the pivot column of each nonzero row and the determinant of the `cols` by `cols`
+/// leading block when it has full rank.
+/// Zero tests go through [`PartialEq`], so [`F64`] treats entries within epsilon of zero as zero,
+/// and the pivot among the nonzero entries is chosen by [`Pivot`].
+///
+/// Complexity: _O(n m w)_ for `n` rows, `m = cols` and `w` columns in total.
+fn eliminate<T>(a: &mut [Vec<T>], cols: usize) -> (Vec<usize>, T)
+where
+    T: Numeric + NumericOps + Clone + PartialEq + Pivot,
+{
+    let n = a.len() - 1;
+    let mut pivots = Vec::new();
+    let mut det = T::ONE;
+    for j in 1..=cols {
+        let i = pivots.len() + 1;
+        if i > n {
+            break;
+        }
+        let mut pivot = None;
+        for r in (i..=n).filter(|&r| a[r][j] != T::ZERO) {
+            if pivot.map_or(true, |p: usize| a[r][j].better_pivot(&a[p][j])) {
+                pivot = Some(r);
+            }
+        }
+        let Some(p) = pivot else {
+            continue;
+        };
+        if p != i {
+            a.swap(i, p);
+            det = T::ZERO - det;
+        }
+        det = det * a[i][j].clone();
+        let inv = T::ONE / a[i][j].clone();
+        for x in a[i].iter_mut().skip(j) {
+            *x = x.clone() * inv.clone();
+        }
+        for r in (1..=n).filter(|&r| r != i) {
+            let f = a[r][j].clone();
+            if f == T::ZERO {
+                continue;
+            }
+            for k in j..a[r].len() {
+                let y = a[i][k].clone();
+                a[r][k] = a[r][k].clone() - f.clone() * y;
+            }
+        }
+        pivots.push(j);
+    }
+    (pivots, det)
+}
+
+/// Gaussian elimination over a field, such as [`Rational`],
+/// [`ModInt`] with a prime modulus or [`F64`], whose pivots are chosen by [`Pivot`].
+///
+/// # Examples
+/// ```
+/// use cplit::linalg::Matrix;
+/// use cplit::num::ModInt998244353 as Mint;
+///
+/// // Number of spanning trees of the complete graph on 4 vertices,
+/// // by the determinant of a minor of its Laplacian.
+/// let l: Vec<Vec<Mint>> = [[0, 0, 0, 0], [0, 3, -1, -1], [0, -1, 3, -1], [0, -1, -1, 3]]
+///     .iter()
+///     .map(|row| row.iter().map(|&x| Mint::from(x)).collect())
+///     .collect();
+/// let l = Matrix::from(l);
+/// assert_eq!(l.det(), Mint::from(16));
+/// assert_eq!(l.rank(), 3);
+/// ```
+impl<T> Matrix<T, PlusTimes>
+where
+    T: Numeric + NumericOps + Clone + PartialEq + Pivot,
+{
+    /// The rank, `0` for the sizeless [`Zero::ZERO`](crate::num::Zero::ZERO)
+    /// and [`One::ONE`](crate::num::One::ONE).
+    ///
+    /// Complexity: _O(n m min(n, m))_.
+    pub fn rank(&self) -> usize {
+        let mut a = self.to_dense(self.rows(), self.cols());
+        eliminate(&mut a, self.cols()).0.len()
+    }
+
+    /// The determinant of a square matrix.
+    ///
+    /// The sizeless [`Zero::ZERO`](crate::num::Zero::ZERO) and [`One::ONE`](crate::num::One::ONE)
+    /// have determinant `T::ZERO` and `T::ONE`.
+    ///
+    /// Complexity: _O(n^3)_.
+    pub fn det(&self) -> T {
+        if let Some(a) = self.as_scalar() {
+            return a.clone();
+        }
+        let n = self.rows();
+        if n != self.cols() {
+            panic!(
+                "Dimension mismatch: cannot take the determinant of a {}x{} matrix",
+                n,
+                self.cols()
+            );
+        }
+        let mut a = self.to_dense(n, n);
+        match eliminate(&mut a, n) {
+            (pivots, det) if pivots.len() == n => det,
+            _ => T::ZERO,
+        }
+    }
+
+    /// The inverse by Gauss-Jordan elimination, or `None` if the matrix is singular.
+    ///
+    /// Complexity: _O(n^3)_.
+    pub fn inv(&self) -> Option<Self> {
+        match self.as_scalar() {
+            Some(a) if *a == T::ZERO => return None,
+            Some(a) => return Some(Self::scalar(T::ONE / a.clone())),
+            None => {}
+        }
+        let n = self.rows();
+        if n != self.cols() {
+            panic!(
+                "Dimension mismatch: cannot invert a {}x{} matrix",
+                n,
+                self.cols()
+            );
+        }
+        let mut a = self.to_dense(n, n);
+        for (row, id) in a.iter_mut().zip(Self::identity(n).to_dense(n, n)) {
+            row.extend(id.into_iter().skip(1));
+        }
+        if eliminate(&mut a, n).0.len() < n {
+            return None;
+        }
+        for row in a.iter_mut() {
+            row.drain(1..=n);
+        }
+        Some(Self::from(a))
+    }
+
+    /// Solves `self * x = b` for `b` padded with index 0,
+    /// or returns `None` if the system is inconsistent.
+    ///
+    /// Complexity: _O(n m min(n, m))_.
+    pub fn solve(&self, b: &[T]) -> Option<Solution<T>> {
+        let (n, m) = (self.rows(), self.cols());
+        if b.len() != n + 1 {
+            panic!(
+                "Dimension mismatch: {}x{} and a vector of length {}",
+                n,
+                m,
+                b.len().saturating_sub(1)
+            );
+        }
+        let mut a = self.to_dense(n, m);
+        for (row, x) in a.iter_mut().zip(b) {
+            row.push(x.clone());
+        }
+        let (pivots, _) = eliminate(&mut a, m);
+        if a[pivots.len() + 1..]
+            .iter()
+            .any(|row| row[m + 1] != T::ZERO)
+        {
+            return None;
+        }
+        let mut particular = vec![T::ZERO; m + 1];
+        let mut is_pivot = vec![false; m + 1];
+        for (row, &j) in a.iter().skip(1).zip(&pivots) {
+            particular[j] = row[m + 1].clone();
+            is_pivot[j] = true;
+        }
+        let basis = (1..=m)
+            .filter(|&f| !is_pivot[f])
+            .map(|f| {
+                let mut v = vec![T::ZERO; m + 1];
+                v[f] = T::ONE;
+                for (row, &j) in a.iter().skip(1).zip(&pivots) {
+                    v[j] = T::ZERO - row[f].clone();
+                }
+                v
+            })
+            .collect();
+        Some(Solution { particular, basis })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fscanln;
+    use crate::linalg::Matrix;
+    use crate::num::{ModInt1000000007, Rational, Zero};
+    use crate::utils::F64;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p3389() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+3
+1 3 4 5
+1 4 7 3
+9 3 2 2
+"#,
+        ));
+        let n: usize;
+        fscanln!(reader, n);
+        let mut a = Matrix::<F64>::new(n, n);
+        let mut b = vec![F64(0.0); n + 1];
+        for i in 1..=n {
+            for j in 1..=n {
+                let x: f64;
+                fscanln!(reader, x);
+                a[(i, j)] = F64(x);
+            }
+            let x: f64;
+            fscanln!(reader, x);
+            b[i] = F64(x);
+        }
+        let s = a.solve(&b).unwrap();
+        assert_eq!(s.dim(), 0);
+        let x: Vec<String> = s.particular[1..]
+            .iter()
+            .map(|x| format!("{:.2}", x.0))
+            .collect();
+        assert_eq!(x, ["-0.97", "5.18", "-2.39"]);
+    }
+
+    #[test]
+    fn free_variables() {
+        type Q = Rational<i64>;
+        let q = |rows: Vec<Vec<i64>>| {
+            Matrix::from(
+                rows.into_iter()
+                    .map(|row| row.into_iter().map(Q::from).collect())
+                    .collect::<Vec<Vec<Q>>>(),
+            )
+        };
+        // x + 2y + z = 4, 2x + 4y + 3z = 9, with y free.
+        let a = q(vec![vec![0; 4], vec![0, 1, 2, 1], vec![0, 2, 4, 3]]);
+        let b = vec![Q::ZERO, Q::from(4), Q::from(9)];
+        let s = a.solve(&b).unwrap();
+        assert_eq!(a.rank(), 2);
+        assert_eq!(s.particular, [0, 3, 0, 1].map(Q::from));
+        assert_eq!(s.basis, vec![[0, -2, 1, 0].map(Q::from).to_vec()]);
+        for v in &s.basis {
+            let x = Matrix::from(v.iter().map(|x| vec![Q::ZERO, *x]).collect::<Vec<_>>());
+            assert_eq!(&a * &x, Matrix::new(2, 1));
+        }
+        let inconsistent = vec![Q::ZERO, Q::from(4), Q::from(9)];
+        assert!(q(vec![vec![0; 3], vec![0, 1, 2], vec![0, 2, 4]])
+            .solve(&inconsistent)
+            .is_none());
+    }
+
+    #[test]
+    fn determinant() {
+        type Mint = ModInt1000000007;
+        let a = Matrix::from(vec![
+            vec![Mint::from(0); 4],
+            vec![Mint::from(0), Mint::from(0), Mint::from(2), Mint::from(1)],
+            vec![Mint::from(0), Mint::from(3), Mint::from(1), Mint::from(4)],
+            vec![Mint::from(0), Mint::from(5), Mint::from(9), Mint::from(2)],
+        ]);
+        // 0 * (2 - 36) - 2 * (6 - 20) + 1 * (27 - 5) = 50.
+        assert_eq!(a.det(), Mint::from(50));
+        assert_eq!(a.transpose().det(), Mint::from(50));
+        assert_eq!((&a * &a).det(), Mint::from(2500));
+        assert_eq!(&a * &a.inv().unwrap(), Matrix::identity(3));
+        assert_eq!(Matrix::<Mint>::new(3, 3).det(), Mint::from(0));
+        assert_eq!(Matrix::<Mint>::ZERO.det(), Mint::from(0));
+    }
+
+    #[test]
+    fn partial_pivoting() {
+        // The first pivot would be 1e-17, losing x[1] entirely to rounding.
+        let a = Matrix::<f64>::from(vec![
+            vec![0.0; 3],
+            vec![0.0, 1e-17, 1.0],
+            vec![0.0, 1.0, 1.0],
+        ]);
+        let s = a.solve(&[0.0, 1.0, 2.0]).unwrap();
+        assert!((s.particular[1] - 1.0).abs() < 1e-9);
+        assert!((s.particular[2] - 1.0).abs() < 1e-9);
+        assert!((a.det() + 1.0).abs() < 1e-9);
+        let inv = a.inv().unwrap();
+        assert!((inv[(1, 1)] + 1.0).abs() < 1e-9 && (inv[(1, 2)] - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,643 @@
+use crate::linalg::{Pivot, PlusTimes, Semiring};
+use crate::num::{Numeric, NumericOps, One, Zero};
+use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+
+/// Dense matrix over the semiring `S`, 1-indexed.
+///
+/// Row 0 and column 0 are buffers, so a grid read by [`fscanln!`](crate::fscanln)
+/// converts directly with [`From`].
+///
+/// [`Zero::ZERO`] and [`One::ONE`] have no size: they act as the zero and identity matrices
+/// of whatever size they are combined with, so that `Matrix` fits in generic code.
+///
+/// Since `ONE` is the identity of any size, `Matrix` can be the value of a
+/// [`SegmentTree`](crate::data_structure::SegmentTree) maintaining range products.
+///
+/// With the default [`PlusTimes`] semiring, it also implements subtraction,
+/// and division by multiplying with the inverse.
+///
+/// # Examples
+/// ```
+/// use cplit::linalg::{Matrix, MinPlus};
+/// use cplit::num::ModInt1000000007 as Mint;
+///
+/// // Fibonacci numbers by fast exponentiation.
+/// let fib = Matrix::<Mint>::from(vec![
+///     vec![Mint::from(0); 3],
+///     vec![Mint::from(0), Mint::from(1), Mint::from(1)],
+///     vec![Mint::from(0), Mint::from(1), Mint::from(0)],
+/// ]);
+/// assert_eq!(fib.pow(90)[(1, 2)], Mint::from(2880067194370816120_u64));
+///
+/// // Shortest paths with exactly 2 edges.
+/// let inf = u64::MAX;
+/// let g = Matrix::<u64, MinPlus>::from(vec![
+///     vec![0; 4],
+///     vec![0, inf, 1, 5],
+///     vec![0, inf, inf, 2],
+///     vec![0, 1, inf, inf],
+/// ]);
+/// assert_eq!(g.pow(2)[(1, 3)], 3);
+/// ```
+pub struct Matrix<T, S = PlusTimes> {
+    repr: Repr<T>,
+    _semiring: PhantomData<S>,
+}
+
+#[derive(Clone)]
+enum Repr<T> {
+    /// A multiple of the identity of any size.
+    Scalar(T),
+    /// Padded with row 0 and column 0.
+    Dense(Vec<Vec<T>>),
+}
+
+use self::Repr::{Dense, Scalar};
+
+impl<T, S> Matrix<T, S>
+where
+    T: Clone,
+    S: Semiring<T>,
+{
+    fn from_repr(repr: Repr<T>) -> Self {
+        Self {
+            repr,
+            _semiring: PhantomData,
+        }
+    }
+
+    /// Constructs a `rows` by `cols` matrix filled with `S::ZERO`.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self::from_repr(Dense(vec![vec![S::ZERO; cols + 1]; rows + 1]))
+    }
+
+    /// Constructs the `n` by `n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::new(n, n);
+        for i in 1..=n {
+            m[(i, i)] = S::ONE;
+        }
+        m
+    }
+
+    /// The number of rows, `0` for the sizeless [`Zero::ZERO`] and [`One::ONE`].
+    pub fn rows(&self) -> usize {
+        match &self.repr {
+            Scalar(_) => 0,
+            Dense(data) => data.len() - 1,
+        }
+    }
+
+    /// The number of columns, `0` for the sizeless [`Zero::ZERO`] and [`One::ONE`].
+    pub fn cols(&self) -> usize {
+        match &self.repr {
+            Scalar(_) => 0,
+            Dense(data) => data[0].len() - 1,
+        }
+    }
+
+    /// The transposed matrix.
+    pub fn transpose(&self) -> Self {
+        match &self.repr {
+            Scalar(_) => self.clone(),
+            Dense(data) => {
+                let mut res = Self::new(self.cols(), self.rows());
+                for (i, row) in data.iter().enumerate().skip(1) {
+                    for (j, x) in row.iter().enumerate().skip(1) {
+                        res[(j, i)] = x.clone();
+                    }
+                }
+                res
+            }
+        }
+    }
+
+    /// Raises a square matrix to the power `exp`.
+    ///
+    /// Complexity: _O(n^3 log exp)_.
+    pub fn pow(&self, mut exp: u64) -> Self {
+        if self.rows() != self.cols() {
+            panic!(
+                "Dimension mismatch: cannot raise a {}x{} matrix to a power",
+                self.rows(),
+                self.cols()
+            );
+        }
+        let mut base = self.clone();
+        let mut res = match &self.repr {
+            Scalar(_) => Self::ONE,
+            Dense(_) => Self::identity(self.rows()),
+        };
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = &res * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        res
+    }
+
+    /// The sizeless multiple `a` of the identity.
+    pub(crate) fn scalar(a: T) -> Self {
+        Self::from_repr(Scalar(a))
+    }
+
+    /// The scalar of the sizeless [`Zero::ZERO`] and [`One::ONE`], if `self` is one of them.
+    pub(crate) fn as_scalar(&self) -> Option<&T> {
+        match &self.repr {
+            Scalar(a) => Some(a),
+            Dense(_) => None,
+        }
+    }
+
+    /// The padded rows, expanding a scalar to `rows` by `cols` with it on the main diagonal.
+    pub(crate) fn to_dense(&self, rows: usize, cols: usize) -> Vec<Vec<T>> {
+        match &self.repr {
+            Scalar(a) => {
+                let mut data = vec![vec![S::ZERO; cols + 1]; rows + 1];
+                for (i, row) in data.iter_mut().enumerate().skip(1).take(cols) {
+                    row[i] = a.clone();
+                }
+                data
+            }
+            Dense(data) => data.clone(),
+        }
+    }
+
+    /// Combines two matrices of the same size element by element.
+    fn zip_with(&self, rhs: &Self, f: impl Fn(T, T) -> T) -> Self {
+        if let (Scalar(a), Scalar(b)) = (&self.repr, &rhs.repr) {
+            return Self::from_repr(Scalar(f(a.clone(), b.clone())));
+        }
+        let (mut a, b) = (
+            self.to_dense(rhs.rows(), rhs.cols()),
+            rhs.to_dense(self.rows(), self.cols()),
+        );
+        if a.len() != b.len() || a[0].len() != b[0].len() {
+            panic!(
+                "Dimension mismatch: {}x{} and {}x{}",
+                a.len() - 1,
+                a[0].len() - 1,
+                b.len() - 1,
+                b[0].len() - 1
+            );
+        }
+        for (ra, rb) in a.iter_mut().zip(b).skip(1) {
+            for (x, y) in ra.iter_mut().zip(rb).skip(1) {
+                *x = f(std::mem::replace(x, S::ZERO), y);
+            }
+        }
+        Self::from_repr(Dense(a))
+    }
+}
+
+impl<T: Clone, S> Clone for Matrix<T, S> {
+    fn clone(&self) -> Self {
+        Self {
+            repr: self.repr.clone(),
+            _semiring: PhantomData,
+        }
+    }
+}
+
+impl<T, S> Zero for Matrix<T, S>
+where
+    S: Semiring<T>,
+{
+    const ZERO: Self = Self {
+        repr: Scalar(S::ZERO),
+        _semiring: PhantomData,
+    };
+}
+
+impl<T, S> One for Matrix<T, S>
+where
+    S: Semiring<T>,
+{
+    const ONE: Self = Self {
+        repr: Scalar(S::ONE),
+        _semiring: PhantomData,
+    };
+}
+
+impl<T, S> Default for Matrix<T, S>
+where
+    S: Semiring<T>,
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<T, S> From<Vec<Vec<T>>> for Matrix<T, S>
+where
+    T: Clone,
+    S: Semiring<T>,
+{
+    /// Constructs a matrix from rows padded with row 0 and column 0.
+    fn from(data: Vec<Vec<T>>) -> Self {
+        if data.is_empty() || data.iter().any(|row| row.len() != data[0].len()) {
+            panic!("The rows of a matrix must be non-empty and of the same length");
+        }
+        Self::from_repr(Dense(data))
+    }
+}
+
+impl<T, S> Index<(usize, usize)> for Matrix<T, S>
+where
+    T: Clone,
+    S: Semiring<T>,
+{
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        match &self.repr {
+            Dense(data) if (1..data.len()).contains(&i) && (1..data[0].len()).contains(&j) => {
+                &data[i][j]
+            }
+            _ => panic!(
+                "Index out of bounds: the size is {}x{} but the index is ({}, {})",
+                self.rows(),
+                self.cols(),
+                i,
+                j
+            ),
+        }
+    }
+}
+
+impl<T, S> IndexMut<(usize, usize)> for Matrix<T, S>
+where
+    T: Clone,
+    S: Semiring<T>,
+{
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        let (rows, cols) = (self.rows(), self.cols());
+        match &mut self.repr {
+            Dense(data) if (1..=rows).contains(&i) && (1..=cols).contains(&j) => &mut data[i][j],
+            _ => panic!(
+                "Index out of bounds: the size is {}x{} but the index is ({}, {})",
+                rows, cols, i, j
+            ),
+        }
+    }
+}
+
+impl<T, S> PartialEq for Matrix<T, S>
+where
+    T: Clone + PartialEq,
+    S: Semiring<T>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if let (Scalar(a), Scalar(b)) = (&self.repr, &other.repr) {
+            return a == b;
+        }
+        // The sizeless one takes the shape of the other.
+        let (a, b) = (
+            self.to_dense(other.rows(), other.cols()),
+            other.to_dense(self.rows(), self.cols()),
+        );
+        // Row 0 and column 0 are buffers and may differ.
+        a.len() == b.len()
+            && a[0].len() == b[0].len()
+            && a.iter().zip(&b).skip(1).all(|(x, y)| x[1..] == y[1..])
+    }
+}
+
+impl<T, S> Mul for &Matrix<T, S>
+where
+    T: Clone,
+    S: Semiring<T>,
+{
+    type Output = Matrix<T, S>;
+
+    /// Complexity: _O(n m k)_.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (a, b) = match (&self.repr, &rhs.repr) {
+            (Scalar(a), Scalar(b)) => {
+                return Matrix::from_repr(Scalar(S::MUL(a.clone(), b.clone())));
+            }
+            (Scalar(a), Dense(_)) => {
+                let mut res = rhs.clone();
+                if let Dense(data) = &mut res.repr {
+                    for x in data
+                        .iter_mut()
+                        .skip(1)
+                        .flat_map(|row| row.iter_mut().skip(1))
+                    {
+                        *x = S::MUL(a.clone(), std::mem::replace(x, S::ZERO));
+                    }
+                }
+                return res;
+            }
+            (Dense(_), Scalar(b)) => {
+                let mut res = self.clone();
+                if let Dense(data) = &mut res.repr {
+                    for x in data
+                        .iter_mut()
+                        .skip(1)
+                        .flat_map(|row| row.iter_mut().skip(1))
+                    {
+                        *x = S::MUL(std::mem::replace(x, S::ZERO), b.clone());
+                    }
+                }
+                return res;
+            }
+            (Dense(a), Dense(b)) => (a, b),
+        };
+        let (n, k, m) = (self.rows(), self.cols(), rhs.cols());
+        if k != rhs.rows() {
+            panic!("Dimension mismatch: {}x{} and {}x{}", n, k, rhs.rows(), m);
+        }
+        let mut res = vec![vec![S::ZERO; m + 1]; n + 1];
+        for i in 1..=n {
+            for l in 1..=k {
+                for j in 1..=m {
+                    let acc = std::mem::replace(&mut res[i][j], S::ZERO);
+                    res[i][j] = S::ADD(acc, S::MUL(a[i][l].clone(), b[l][j].clone()));
+                }
+            }
+        }
+        Matrix::from_repr(Dense(res))
+    }
+}
+
+impl<T, S> Add for &Matrix<T, S>
+where
+    T: Clone,
+    S: Semiring<T>,
+{
+    type Output = Matrix<T, S>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, S::ADD)
+    }
+}
+
+impl<T> Sub for &Matrix<T, PlusTimes>
+where
+    T: Numeric + NumericOps + Clone,
+{
+    type Output = Matrix<T, PlusTimes>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+
+impl<T> Div for &Matrix<T, PlusTimes>
+where
+    T: Numeric + NumericOps + Clone + PartialEq + Pivot,
+{
+    type Output = Matrix<T, PlusTimes>;
+
+    /// Multiplies by the inverse of `rhs`.
+    ///
+    /// Panics if `rhs` is singular.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * &rhs.inv().expect("The matrix is singular")
+    }
+}
+
+impl<T, S> Mul for Matrix<T, S>
+where
+    T: Clone,
+    S: Semiring<T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<T, S> Add for Matrix<T, S>
+where
+    T: Clone,
+    S: Semiring<T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<T> Sub for Matrix<T, PlusTimes>
+where
+    T: Numeric + NumericOps + Clone,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl<T> Div for Matrix<T, PlusTimes>
+where
+    T: Numeric + NumericOps + Clone + PartialEq + Pivot,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        &self / &rhs
+    }
+}
+
+impl<T: Display, S> Display for Matrix<T, S> {
+    /// Prints the rows on separate lines, with elements separated by spaces.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.repr {
+            Scalar(a) => Display::fmt(a, f),
+            Dense(data) => {
+                for (i, row) in data.iter().enumerate().skip(1) {
+                    if i > 1 {
+                        writeln!(f)?;
+                    }
+                    for (j, x) in row.iter().enumerate().skip(1) {
+                        if j > 1 {
+                            write!(f, " ")?;
+                        }
+                        Display::fmt(x, f)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<T: Debug, S> Debug for Matrix<T, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.repr {
+            Scalar(a) => f.debug_tuple("Scalar").field(a).finish(),
+            Dense(data) => f
+                .debug_list()
+                .entries(data.iter().skip(1).map(|row| &row[1..]))
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::segment_tree::ops::{AddSum, Operation};
+    use crate::data_structure::{BottomUpSegmentTree, SegmentTree};
+    use crate::fscanln;
+    use crate::linalg::{Matrix, MaxPlus, MinPlus};
+    use crate::num::{ModInt1000000007, One, Rational, Zero};
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p3390() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+3 1000000000000
+1 2 3
+4 5 6
+7 8 9
+"#,
+        ));
+        type Mint = ModInt1000000007;
+        let (n, k): (usize, u64);
+        fscanln!(reader, n, k);
+        let a: Vec<Vec<Mint>>;
+        fscanln!(reader, a; n, n);
+        let a = Matrix::from(a);
+        let r = a.pow(k);
+        assert_eq!(
+            r.to_string(),
+            "290201168 297011557 303821946\n\
+             773175421 11257346 249339278\n\
+             256149667 725503142 194856610"
+        );
+        assert_eq!(a.pow(0), Matrix::identity(n));
+        assert_eq!(&a * &Matrix::ONE, a);
+        assert_eq!(&a + &Matrix::ZERO, a);
+        assert_eq!(&(&a - &a) * &a, Matrix::ZERO);
+    }
+
+    #[test]
+    fn sizeless_constants() {
+        let a = Matrix::<i64>::from(vec![vec![0; 4], vec![0, 1, 2, 3], vec![0, 4, 5, 6]]);
+        assert_eq!(&a + &Matrix::ZERO, a);
+        assert_eq!(&Matrix::ZERO + &a, a);
+        assert_eq!(&a - &a, Matrix::ZERO);
+        assert_eq!(&Matrix::ONE * &a, a);
+        assert_eq!(&a * &Matrix::ONE, a);
+        assert_eq!(&(&a * &Matrix::ZERO) + &a, a);
+    }
+
+    #[test]
+    fn tropical() {
+        // Cow Relays: shortest walk from 1 to 4 with exactly k edges.
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+4 5 3 1 4
+1 2 3
+2 3 4
+3 4 2
+1 3 9
+2 4 10
+"#,
+        ));
+        let (n, m, k, s, t): (usize, usize, u64, usize, usize);
+        fscanln!(reader, n, m, k, s, t);
+        let mut g = Matrix::<u64, MinPlus>::new(n, n);
+        let mut h = Matrix::<i64, MaxPlus>::new(n, n);
+        for _ in 0..m {
+            let (u, v, w): (usize, usize, u64);
+            fscanln!(reader, u, v, w);
+            for (x, y) in [(u, v), (v, u)] {
+                g[(x, y)] = g[(x, y)].min(w);
+                h[(x, y)] = h[(x, y)].max(w as i64);
+            }
+        }
+        assert_eq!(g.pow(k)[(s, t)], 9);
+        assert_eq!(g.pow(2)[(s, t)], 11);
+        assert_eq!(h.pow(k)[(s, t)], 23);
+        assert_eq!(g.pow(0)[(s, s)], 0);
+        assert_eq!(g.pow(0)[(s, t)], u64::MAX);
+    }
+
+    #[test]
+    fn inverse() {
+        type Q = Rational<i64>;
+        let a = Matrix::from(vec![
+            vec![Q::ZERO; 4],
+            vec![Q::ZERO, Q::from(2), Q::from(1), Q::from(1)],
+            vec![Q::ZERO, Q::from(1), Q::from(3), Q::from(2)],
+            vec![Q::ZERO, Q::from(1), Q::from(0), Q::from(0)],
+        ]);
+        let inv = a.inv().unwrap();
+        assert_eq!(&a * &inv, Matrix::ONE);
+        assert_eq!(inv[(1, 3)], Q::from(1));
+        assert_eq!(inv[(2, 2)], Q::from(1));
+        assert_eq!(&a / &a, Matrix::identity(3));
+
+        let singular = Matrix::from(vec![
+            vec![Q::ZERO; 3],
+            vec![Q::ZERO, Q::from(1), Q::from(2)],
+            vec![Q::ZERO, Q::from(2), Q::from(4)],
+        ]);
+        assert!(singular.inv().is_none());
+    }
+
+    #[test]
+    fn segment_tree_values() {
+        type Mint = ModInt1000000007;
+        struct Product;
+        impl Operation<Matrix<Mint>, ()> for Product {
+            const COMBINE: fn(Matrix<Mint>, Matrix<Mint>) -> Matrix<Mint> = |a, b| a * b;
+            const PUSH_VAL: fn(Matrix<Mint>, (), usize) -> Matrix<Mint> = |val, _, _| val;
+            const PUSH_TAG: fn((), ()) -> () = |_, _| ();
+            const TAG_IDENTITY: () = ();
+            const VAL_IDENTITY: Matrix<Mint> = Matrix::ONE;
+        }
+        let m = |a: u64, b: u64, c: u64, d: u64| {
+            Matrix::from(vec![
+                vec![Mint::ZERO; 3],
+                vec![Mint::ZERO, Mint::from(a), Mint::from(b)],
+                vec![Mint::ZERO, Mint::from(c), Mint::from(d)],
+            ])
+        };
+        let a = vec![Matrix::ZERO, m(1, 1, 0, 1), m(0, 1, 1, 0), m(2, 0, 0, 3)];
+        let mut st = SegmentTree::<_, _, Product>::from(a.clone());
+        assert_eq!(st.query(..), m(2, 3, 2, 0));
+        assert_eq!(st.query(2..=3), m(0, 3, 2, 0));
+        st.set(2, Matrix::identity(2));
+        assert_eq!(st.query(1..=2), m(1, 1, 0, 1));
+        assert_eq!(st.query(..), m(2, 3, 0, 3));
+
+        let mut sum = BottomUpSegmentTree::<_, AddSum>::from(a);
+        assert_eq!(sum.query(1..=3), m(3, 2, 1, 4));
+        sum.set(1, Matrix::ZERO);
+        assert_eq!(sum.query(..), m(2, 1, 1, 3));
+    }
+
+    #[test]
+    fn eq_ignores_buffers() {
+        let a = Matrix::<i64>::from(vec![vec![0, 0, 0], vec![0, 1, 2], vec![0, 3, 4]]);
+        let b = Matrix::<i64>::from(vec![vec![7, 8, 9], vec![5, 1, 2], vec![6, 3, 4]]);
+        assert_eq!(a, b);
+        assert_ne!(
+            a,
+            Matrix::from(vec![vec![0, 0, 0], vec![0, 1, 2], vec![0, 3, 5]])
+        );
+        assert_ne!(a, Matrix::from(vec![vec![0, 0], vec![0, 1], vec![0, 3]]));
+        assert_ne!(a, Matrix::new(2, 3));
+        assert_eq!(b, Matrix::identity(2) * a.clone());
+        assert_ne!(b, Matrix::ONE);
+        assert_eq!(
+            Matrix::<i64>::from(vec![vec![9, 9], vec![9, 1]]),
+            Matrix::ONE
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//! Linear Algebra
+//!
+//! Matrices over an arbitrary [`Semiring`],
+//! and Gaussian elimination over fields and over GF(2) with [`BitMatrix`].
+//!
+//! Indices are 1-based as in the rest of the library.
+
+pub mod bit_matrix;
+pub mod gauss;
+pub mod matrix;
+pub mod semiring;
+pub mod square_matrix;
+
+#[doc(inline)]
+pub use self::bit_matrix::BitMatrix;
+#[doc(inline)]
+pub use self::gauss::{Pivot, Solution};
+#[doc(inline)]
+pub use self::matrix::Matrix;
+#[doc(inline)]
+pub use self::semiring::{MaxPlus, MinPlus, PlusTimes, Semiring};
+#[doc(inline)]
+pub use self::square_matrix::SquareMatrix;
@@ -0,0 +1,78 @@
+use crate::num::{Bounded, Numeric, NumericCmpOps, NumericOps, Zero};
+
+/// The abstract semiring of matrix elements.
+///
+/// `ADD` must be associative and commutative with identity `ZERO`,
+/// and `MUL` must be associative with identity `ONE`, distribute over `ADD`
+/// and be absorbed by `ZERO`.
+pub trait Semiring<T> {
+    /// Identity of `ADD`, absorbing for `MUL`.
+    const ZERO: T;
+
+    /// Identity of `MUL`.
+    const ONE: T;
+
+    /// The semiring addition.
+    const ADD: fn(T, T) -> T;
+
+    /// The semiring multiplication.
+    const MUL: fn(T, T) -> T;
+}
+
+/// The usual `(+, *)` semiring, for linear recurrences and path counting.
+#[derive(Debug)]
+pub struct PlusTimes;
+
+impl<T> Semiring<T> for PlusTimes
+where
+    T: Numeric + NumericOps,
+{
+    const ZERO: T = T::ZERO;
+    const ONE: T = T::ONE;
+    const ADD: fn(T, T) -> T = |a, b| a + b;
+    const MUL: fn(T, T) -> T = |a, b| a * b;
+}
+
+/// The tropical `(min, +)` semiring, for shortest paths with exactly `k` edges.
+///
+/// `T::MAX` stands for infinity.
+#[derive(Debug)]
+pub struct MinPlus;
+
+impl<T> Semiring<T> for MinPlus
+where
+    T: Zero + Bounded + NumericOps + NumericCmpOps,
+{
+    const ZERO: T = T::MAX;
+    const ONE: T = T::ZERO;
+    const ADD: fn(T, T) -> T = |a, b| a.min(b);
+    const MUL: fn(T, T) -> T = |a, b| {
+        if a == T::MAX || b == T::MAX {
+            T::MAX
+        } else {
+            a + b
+        }
+    };
+}
+
+/// The `(max, +)` semiring, for longest paths with exactly `k` edges.
+///
+/// `T::MIN` stands for negative infinity.
+#[derive(Debug)]
+pub struct MaxPlus;
+
+impl<T> Semiring<T> for MaxPlus
+where
+    T: Zero + Bounded + NumericOps + NumericCmpOps,
+{
+    const ZERO: T = T::MIN;
+    const ONE: T = T::ZERO;
+    const ADD: fn(T, T) -> T = |a, b| a.max(b);
+    const MUL: fn(T, T) -> T = |a, b| {
+        if a == T::MIN || b == T::MIN {
+            T::MIN
+        } else {
+            a + b
+        }
+    };
+}
@@ -0,0 +1,310 @@
+use crate::linalg::{Matrix, Pivot, PlusTimes, Semiring};
+use crate::num::{Numeric, NumericOps, One, Zero};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+
+/// `N` by `N` matrix over the semiring `S` stored inline, 1-indexed.
+///
+/// Unlike [`Matrix`], it is [`Copy`] when `T` is,
+/// so it can be the value or tag type of a [`SegmentTree`](crate::data_structure::SegmentTree).
+///
+/// # Examples
+/// ```
+/// use cplit::linalg::SquareMatrix;
+/// use cplit::num::{One, Zero};
+///
+/// let a = SquareMatrix::<i64, 2>::from([[1, 1], [1, 0]]);
+/// assert_eq!(a.pow(10)[(1, 2)], 55);
+/// assert_eq!(a * SquareMatrix::ONE, a);
+/// assert_eq!(a - a, SquareMatrix::ZERO);
+/// ```
+pub struct SquareMatrix<T, const N: usize, S = PlusTimes> {
+    data: [[T; N]; N],
+    _semiring: PhantomData<S>,
+}
+
+impl<T, const N: usize, S> SquareMatrix<T, N, S>
+where
+    T: Copy,
+    S: Semiring<T>,
+{
+    /// The transposed matrix.
+    pub fn transpose(&self) -> Self {
+        Self::from(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.data[j][i])
+        }))
+    }
+
+    /// Raises `self` to the power `exp`.
+    ///
+    /// Complexity: _O(N^3 log exp)_.
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut res = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = res * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        res
+    }
+}
+
+impl<T, const N: usize> SquareMatrix<T, N, PlusTimes>
+where
+    T: Numeric + NumericOps + Copy + PartialEq + Pivot,
+{
+    /// The determinant by Gaussian elimination.
+    ///
+    /// Complexity: _O(N^3)_.
+    pub fn det(&self) -> T {
+        Matrix::from(*self).det()
+    }
+
+    /// The inverse by Gauss-Jordan elimination, or `None` if the matrix is singular.
+    ///
+    /// Complexity: _O(N^3)_.
+    pub fn inv(&self) -> Option<Self> {
+        let inv = Matrix::from(*self).inv()?;
+        Some(Self::from(std::array::from_fn(|i| {
+            std::array::from_fn(|j| inv[(i + 1, j + 1)])
+        })))
+    }
+}
+
+impl<T: Copy, const N: usize, S> Clone for SquareMatrix<T, N, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy, const N: usize, S> Copy for SquareMatrix<T, N, S> {}
+
+impl<T, const N: usize, S> Zero for SquareMatrix<T, N, S>
+where
+    T: Copy,
+    S: Semiring<T>,
+{
+    const ZERO: Self = Self {
+        data: [[S::ZERO; N]; N],
+        _semiring: PhantomData,
+    };
+}
+
+impl<T, const N: usize, S> One for SquareMatrix<T, N, S>
+where
+    T: Copy,
+    S: Semiring<T>,
+{
+    const ONE: Self = {
+        let mut data = [[S::ZERO; N]; N];
+        let mut i = 0;
+        while i < N {
+            data[i][i] = S::ONE;
+            i += 1;
+        }
+        Self {
+            data,
+            _semiring: PhantomData,
+        }
+    };
+}
+
+impl<T, const N: usize, S> Default for SquareMatrix<T, N, S>
+where
+    T: Copy,
+    S: Semiring<T>,
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<T, const N: usize, S> From<[[T; N]; N]> for SquareMatrix<T, N, S> {
+    fn from(data: [[T; N]; N]) -> Self {
+        Self {
+            data,
+            _semiring: PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize, S> From<SquareMatrix<T, N, S>> for Matrix<T, S>
+where
+    T: Copy,
+    S: Semiring<T>,
+{
+    fn from(m: SquareMatrix<T, N, S>) -> Self {
+        let mut res = Matrix::new(N, N);
+        for i in 1..=N {
+            for j in 1..=N {
+                res[(i, j)] = m[(i, j)];
+            }
+        }
+        res
+    }
+}
+
+impl<T, const N: usize, S> Index<(usize, usize)> for SquareMatrix<T, N, S> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        if !(1..=N).contains(&i) || !(1..=N).contains(&j) {
+            panic!(
+                "Index out of bounds: the size is {}x{} but the index is ({}, {})",
+                N, N, i, j
+            );
+        }
+        &self.data[i - 1][j - 1]
+    }
+}
+
+impl<T, const N: usize, S> IndexMut<(usize, usize)> for SquareMatrix<T, N, S> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        if !(1..=N).contains(&i) || !(1..=N).contains(&j) {
+            panic!(
+                "Index out of bounds: the size is {}x{} but the index is ({}, {})",
+                N, N, i, j
+            );
+        }
+        &mut self.data[i - 1][j - 1]
+    }
+}
+
+impl<T: PartialEq, const N: usize, S> PartialEq for SquareMatrix<T, N, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T: Eq, const N: usize, S> Eq for SquareMatrix<T, N, S> {}
+
+impl<T, const N: usize, S> Mul for SquareMatrix<T, N, S>
+where
+    T: Copy,
+    S: Semiring<T>,
+{
+    type Output = Self;
+
+    /// Complexity: _O(N^3)_.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut res = Self::ZERO;
+        for i in 0..N {
+            for l in 0..N {
+                for j in 0..N {
+                    res.data[i][j] =
+                        S::ADD(res.data[i][j], S::MUL(self.data[i][l], rhs.data[l][j]));
+                }
+            }
+        }
+        res
+    }
+}
+
+impl<T, const N: usize, S> Add for SquareMatrix<T, N, S>
+where
+    T: Copy,
+    S: Semiring<T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from(std::array::from_fn(|i| {
+            std::array::from_fn(|j| S::ADD(self.data[i][j], rhs.data[i][j]))
+        }))
+    }
+}
+
+impl<T, const N: usize> Sub for SquareMatrix<T, N, PlusTimes>
+where
+    T: Numeric + NumericOps + Copy,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.data[i][j] - rhs.data[i][j])
+        }))
+    }
+}
+
+impl<T, const N: usize> Div for SquareMatrix<T, N, PlusTimes>
+where
+    T: Numeric + NumericOps + Copy + PartialEq + Pivot,
+{
+    type Output = Self;
+
+    /// Multiplies by the inverse of `rhs`.
+    ///
+    /// Panics if `rhs` is singular.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv().expect("The matrix is singular")
+    }
+}
+
+impl<T: Debug, const N: usize, S> Debug for SquareMatrix<T, N, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::segment_tree::Operation;
+    use crate::data_structure::SegmentTree;
+    use crate::fscanln;
+    use crate::linalg::SquareMatrix;
+    use crate::num::{ModInt998244353, One};
+    use std::io::{BufReader, Cursor};
+
+    type Mint = ModInt998244353;
+    type Mat = SquareMatrix<Mint, 2>;
+
+    /// Ordered product of matrices, without range updates.
+    struct Product;
+
+    impl Operation<Mat, ()> for Product {
+        const COMBINE: fn(Mat, Mat) -> Mat = |left_val, right_val| left_val * right_val;
+        const PUSH_VAL: fn(Mat, (), usize) -> Mat = |val, _, _| val;
+        const PUSH_TAG: fn((), ()) -> () = |_, _| ();
+        const VAL_IDENTITY: Mat = Mat::ONE;
+        const TAG_IDENTITY: () = ();
+    }
+
+    #[test]
+    fn segment_tree_of_affine_maps() {
+        // Composing `x -> a x + b` as `[[a, b], [0, 1]]`, applied right to left.
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+4
+2 1
+3 0
+1 5
+2 2
+"#,
+        ));
+        let n: usize;
+        fscanln!(reader, n);
+        let mut v = vec![Mat::ONE];
+        for _ in 0..n {
+            let (a, b): (Mint, Mint);
+            fscanln!(reader, a, b);
+            v.push(Mat::from([[a, b], [Mint::from(0), Mint::from(1)]]));
+        }
+        let mut st = SegmentTree::<Mat, (), Product>::from(v.clone());
+        let apply = |m: Mat, x: u64| (m[(1, 1)] * Mint::from(x) + m[(1, 2)]).val();
+        // 2 * (3 * x + 0) + 1 with x = 1.
+        assert_eq!(apply(st.query(1..=2), 1), 7);
+        // 3 * (1 * (2 * x + 2) + 5) + 0 with x = 1.
+        assert_eq!(apply(st.query(2..=4), 1), 27);
+        assert_eq!(st.query(..), v[1] * v[2] * v[3] * v[4]);
+        assert_eq!(v[1] / v[1], Mat::ONE);
+        assert_eq!((v[1] * v[2]).det(), v[1].det() * v[2].det());
+        assert_eq!(v[4].pow(3), v[4] * v[4] * v[4]);
+        assert_eq!(v[2].transpose()[(2, 1)], Mint::from(0));
+    }
+}
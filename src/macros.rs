@@ -1,87 +1,157 @@
+/// Reads values from a [`BufRead`](std::io::BufRead) through a [`Scanner`](crate::io::Scanner).
+///
+/// Tokens left unread on a line are kept in the reader for the next call.
+/// Every value is read through [`Readable`](crate::io::Readable),
+/// so tuples, fixed-size arrays, `char` cells and byte strings work as well as primitives.
+///
+/// - `fscanln!(reader, a, b, c)` reads the next tokens, possibly spanning several lines.
+/// - `fscanln!(reader, a, b, c; ?)` reads the tokens of the next non-empty line,
+///   missing ones are set to `Default::default()`.
+/// - `fscanln!(reader, v; n)` reads `n` values into a 1-based collection.
+/// - `fscanln!(reader, g; n, m)` reads an `n x m` grid, padded with row 0 and column 0.
+/// - `fscanln!(reader, v;)` reads the rest of the line (or the next line if nothing is left)
+///   into a 1-based collection.
 #[macro_export]
 macro_rules!  fscanln {
     ($reader:expr, $($i:expr), +) => {{
-        #[allow(unused_imports)]
-        use std::io::BufRead;
-        let mut iter = std::iter::repeat_with(|| {
-            let mut buf = String::new();
-            $reader.read_line(&mut buf).unwrap();
-            buf.split_whitespace()
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>()
-        })
-        .flatten();
-        $(
-            $i = iter.next().unwrap().parse().unwrap();
-        )*
+        match &mut $reader {
+            reader => {
+                let mut scanner = $crate::io::Scanner::new(reader);
+                $(
+                    $i = scanner.token();
+                )*
+            }
+        }
     }};
 
     ( $reader:expr, $($i:expr), +; ?) => {{
-        #[allow(unused_imports)]
-        use std::io::BufRead;
-        let mut iter = std::iter::repeat_with(|| {
-            let mut buf = String::new();
-            $reader.read_line(&mut buf).unwrap();
-            buf.split_whitespace()
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>()
-        })
-        .find(|x| !x.is_empty())
-        .unwrap()
-        .into_iter();
-        $(
-            if let Some(val) = iter.next() {
-                $i = val.parse().unwrap();
-            } else {
-                $i = Default::default();
+        match &mut $reader {
+            reader => {
+                let mut scanner = $crate::io::Scanner::new(reader);
+                scanner.skip_whitespace();
+                $(
+                    $i = scanner.line_token().unwrap_or_default();
+                )*
+            }
+        }
+    }};
+
+    ($reader:expr, $coll:expr ; $n:expr, $m:expr) => {{
+        match &mut $reader {
+            reader => {
+                let mut scanner = $crate::io::Scanner::new(reader);
+                $coll = scanner.grid($n, $m);
             }
-        )*
+        }
     }};
 
     ($reader:expr, $coll:expr ; $n:expr) => {{
-        #[allow(unused_imports)]
-        use std::io::BufRead;
-        $coll = std::iter::once(Default::default())
-                .chain(
-                    std::iter::repeat_with(|| {
-                        let mut buf = String::new();
-                        $reader.read_line(&mut buf).unwrap();
-                        buf.split_whitespace()
-                            .map(|x| x.parse().unwrap())
-                            .collect::<Vec<_>>()
-                    })
-                    .flatten()
-                    .take($n),
-                )
-                .collect();
+        match &mut $reader {
+            reader => {
+                let mut scanner = $crate::io::Scanner::new(reader);
+                $coll = scanner.vec($n).into_iter().collect();
+            }
+        }
     }};
 
     ($reader:expr, $coll:expr ;) => {{
-        #[allow(unused_imports)]
-        use std::io::BufRead;
-        let mut buf = String::new();
-        $reader.read_line(&mut buf).unwrap();
-        $coll = std::iter::once(Default::default())
-                .chain(buf.split_whitespace().map(|x| x.parse().unwrap()))
-                .collect();
+        match &mut $reader {
+            reader => {
+                let mut scanner = $crate::io::Scanner::new(reader);
+                $coll = scanner.line().into_iter().collect();
+            }
+        }
     }};
 }
 
+/// Reads values from the standard input, see [`fscanln!`](crate::fscanln).
 #[macro_export]
 macro_rules! scanln {
     ($($i:expr), +) => {
-        $crate::fscanln!(std::io::stdin(), $($i), +);
+        $crate::fscanln!(std::io::stdin().lock(), $($i), +);
     };
 
     ($($i:expr), +; ?) => {
-        $crate::fscanln!(std::io::stdin(), $($i), +; ?);
+        $crate::fscanln!(std::io::stdin().lock(), $($i), +; ?);
+    };
+
+    ($coll:expr ; $n:expr, $m:expr) => {
+        $crate::fscanln!(std::io::stdin().lock(), $coll ; $n, $m);
     };
 
     ($coll:expr ; $n:expr) => {
-        $crate::fscanln!(std::io::stdin(), $coll ; $n);
+        $crate::fscanln!(std::io::stdin().lock(), $coll ; $n);
     };
 
     ($coll:expr ;) => {
-        $crate::fscanln!(std::io::stdin(), $coll ;);
+        $crate::fscanln!(std::io::stdin().lock(), $coll ;);
+    };
+}
+
+/// Writes values into a [`Write`](std::io::Write), usually a [`Writer`](crate::io::Writer),
+/// followed by a line break.
+///
+/// - `fprintln!(writer, a, b, c)` writes the values separated by spaces.
+/// - `fprintln!(writer, v;)` writes a 1-based collection without index 0, separated by spaces.
+/// - `fprintln!(writer, v; sep)` writes a 1-based collection without index 0, separated by `sep`.
+/// - `fprintln!(writer)` writes an empty line.
+#[macro_export]
+macro_rules! fprintln {
+    ($writer:expr) => {{
+        #[allow(unused_imports)]
+        use std::io::Write;
+        writeln!($writer).unwrap();
+    }};
+
+    ($writer:expr, $first:expr $(, $i:expr)*) => {{
+        #[allow(unused_imports)]
+        use std::io::Write;
+        match &mut $writer {
+            writer => {
+                write!(writer, "{}", $first).unwrap();
+                $(
+                    write!(writer, " {}", $i).unwrap();
+                )*
+                writeln!(writer).unwrap();
+            }
+        }
+    }};
+
+    ($writer:expr, $coll:expr ;) => {
+        $crate::fprintln!($writer, $coll ; " ");
+    };
+
+    ($writer:expr, $coll:expr ; $sep:expr) => {{
+        #[allow(unused_imports)]
+        use std::io::Write;
+        match &mut $writer {
+            writer => {
+                $crate::io::writer::join(writer, (&$coll).into_iter().skip(1), $sep);
+                writeln!(writer).unwrap();
+            }
+        }
+    }};
+}
+
+/// Writes values into the standard output, see [`fprintln!`](crate::fprintln).
+///
+/// Each call flushes on its own, use a [`Writer`](crate::io::Writer) with
+/// [`fprintln!`](crate::fprintln) for large outputs.
+#[macro_export]
+macro_rules! printv {
+    () => {
+        $crate::fprintln!($crate::io::Writer::stdout());
+    };
+
+    ($($i:expr), +) => {
+        $crate::fprintln!($crate::io::Writer::stdout(), $($i), +);
+    };
+
+    ($coll:expr ;) => {
+        $crate::fprintln!($crate::io::Writer::stdout(), $coll ;);
+    };
+
+    ($coll:expr ; $sep:expr) => {
+        $crate::fprintln!($crate::io::Writer::stdout(), $coll ; $sep);
     };
 }
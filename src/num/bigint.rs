@@ -0,0 +1,800 @@
+use crate::io::{Readable, Scanner};
+use crate::num::{LowerBounded, MaybeBounded, One, Zero};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+use std::io::BufRead;
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+/// Base of the limbs.
+const BASE: u64 = 1_000_000_000;
+/// Decimal digits per limb.
+const WIDTH: usize = 9;
+/// Below this many limbs, multiplication is done in the schoolbook way.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Arbitrary-precision unsigned integer.
+///
+/// Stored as little-endian limbs in base `10^9` without leading zero limbs,
+/// so parsing and printing in base 10 take linear time.
+/// Multiplication switches to Karatsuba for large operands,
+/// and division is Knuth's algorithm D.
+///
+/// Subtracting a larger number panics.
+///
+/// # Examples
+/// ```
+/// use cplit::num::BigUint;
+///
+/// let a: BigUint = "123456789012345678901234567890".parse().unwrap();
+/// let b = BigUint::from(987654321_u32);
+/// assert_eq!((&a * &b).to_string(), "121932631124828532112482853211126352690");
+/// assert_eq!((&a / &b).to_string(), "124999998873437499901");
+/// assert_eq!((&a % &b).to_string(), "574845669");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    digits: Cow<'static, [u32]>,
+}
+
+/// Arbitrary-precision signed integer, as a sign and a [`BigUint`] magnitude.
+///
+/// Division truncates toward zero and the remainder takes the sign of the dividend,
+/// the same as primitive integers.
+///
+/// # Examples
+/// ```
+/// use cplit::num::BigInt;
+///
+/// let a: BigInt = "-100000000000000000000".parse().unwrap();
+/// let b = BigInt::from(7);
+/// assert_eq!((&a / &b).to_string(), "-14285714285714285714");
+/// assert_eq!((&a % &b).to_string(), "-2");
+/// assert!(a < b);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    neg: bool,
+    mag: BigUint,
+}
+
+fn trim(mut v: Vec<u32>) -> Vec<u32> {
+    while v.last() == Some(&0) {
+        v.pop();
+    }
+    v
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut res = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for (i, &x) in a.iter().enumerate() {
+        let cur = x as u64 + b.get(i).map_or(0, |&y| y as u64) + carry;
+        res.push((cur % BASE) as u32);
+        carry = cur / BASE;
+    }
+    if carry > 0 {
+        res.push(carry as u32);
+    }
+    res
+}
+
+/// `a - b`, requires `a >= b`.
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut res = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &x) in a.iter().enumerate() {
+        let mut cur = x as i64 - b.get(i).map_or(0, |&y| y as i64) - borrow;
+        borrow = 0;
+        if cur < 0 {
+            cur += BASE as i64;
+            borrow = 1;
+        }
+        res.push(cur as u32);
+    }
+    trim(res)
+}
+
+fn mul_small(a: &[u32], d: u32) -> Vec<u32> {
+    let mut res = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for &x in a {
+        let cur = x as u64 * d as u64 + carry;
+        res.push((cur % BASE) as u32);
+        carry = cur / BASE;
+    }
+    if carry > 0 {
+        res.push(carry as u32);
+    }
+    trim(res)
+}
+
+fn mul_schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut res = vec![0_u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let cur = res[i + j] as u64 + x as u64 * y as u64 + carry;
+            res[i + j] = (cur % BASE) as u32;
+            carry = cur / BASE;
+        }
+        let mut k = i + b.len();
+        while carry > 0 {
+            let cur = res[k] as u64 + carry;
+            res[k] = (cur % BASE) as u32;
+            carry = cur / BASE;
+            k += 1;
+        }
+    }
+    trim(res)
+}
+
+/// Adds `b * BASE^shift` into `acc`.
+fn add_shifted(acc: &mut Vec<u32>, b: &[u32], shift: usize) {
+    if acc.len() < b.len() + shift + 1 {
+        acc.resize(b.len() + shift + 1, 0);
+    }
+    let mut carry = 0;
+    let mut i = 0;
+    while i < b.len() || carry > 0 {
+        let cur = acc[i + shift] as u64 + b.get(i).map_or(0, |&x| x as u64) + carry;
+        acc[i + shift] = (cur % BASE) as u32;
+        carry = cur / BASE;
+        i += 1;
+    }
+}
+
+fn mul_karatsuba(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+    let m = a.len().max(b.len()) / 2;
+    let (a0, a1) = a.split_at(m.min(a.len()));
+    let (b0, b1) = b.split_at(m.min(b.len()));
+    let (a0, b0) = (trim(a0.to_vec()), trim(b0.to_vec()));
+    let z0 = mul_karatsuba(&a0, &b0);
+    let z2 = mul_karatsuba(a1, b1);
+    let z1 = mul_karatsuba(&add_mag(&a0, a1), &add_mag(&b0, b1));
+    let z1 = sub_mag(&sub_mag(&z1, &z0), &z2);
+    let mut res = z0;
+    add_shifted(&mut res, &z1, m);
+    add_shifted(&mut res, &z2, 2 * m);
+    trim(res)
+}
+
+fn divmod_small(a: &[u32], d: u32) -> (Vec<u32>, u32) {
+    let mut res = vec![0; a.len()];
+    let mut rem = 0_u64;
+    for i in (0..a.len()).rev() {
+        let cur = rem * BASE + a[i] as u64;
+        res[i] = (cur / d as u64) as u32;
+        rem = cur % d as u64;
+    }
+    (trim(res), rem as u32)
+}
+
+/// Knuth's algorithm D in base `10^9`.
+fn divmod_mag(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if v.is_empty() {
+        panic!("attempt to divide by zero");
+    }
+    if cmp_mag(u, v) == Ordering::Less {
+        return (vec![], u.to_vec());
+    }
+    if v.len() == 1 {
+        let (q, r) = divmod_small(u, v[0]);
+        return (q, trim(vec![r]));
+    }
+    let n = v.len();
+    let m = u.len() - n;
+    // Normalize so that the top limb of the divisor is at least BASE / 2.
+    let d = (BASE / (v[n - 1] as u64 + 1)) as u32;
+    let mut un = mul_small(u, d);
+    un.resize(u.len() + 1, 0);
+    let vn = mul_small(v, d);
+    let (vt, vs) = (vn[n - 1] as u64, vn[n - 2] as u64);
+    let mut q = vec![0; m + 1];
+    for j in (0..=m).rev() {
+        let num = un[j + n] as u64 * BASE + un[j + n - 1] as u64;
+        let mut qhat = num / vt;
+        let mut rhat = num % vt;
+        while qhat >= BASE || qhat * vs > rhat * BASE + un[j + n - 2] as u64 {
+            qhat -= 1;
+            rhat += vt;
+            if rhat >= BASE {
+                break;
+            }
+        }
+        let mut borrow = 0_i64;
+        let mut carry = 0_u64;
+        for i in 0..n {
+            let p = qhat * vn[i] as u64 + carry;
+            carry = p / BASE;
+            let t = un[i + j] as i64 - (p % BASE) as i64 + borrow;
+            (un[i + j], borrow) = if t < 0 {
+                ((t + BASE as i64) as u32, -1)
+            } else {
+                (t as u32, 0)
+            };
+        }
+        let t = un[j + n] as i64 - carry as i64 + borrow;
+        if t < 0 {
+            // `qhat` was one too large, add the divisor back.
+            un[j + n] = (t + BASE as i64) as u32;
+            qhat -= 1;
+            let mut carry = 0;
+            for i in 0..n {
+                let cur = un[i + j] as u64 + vn[i] as u64 + carry;
+                un[i + j] = (cur % BASE) as u32;
+                carry = cur / BASE;
+            }
+            un[j + n] = ((un[j + n] as u64 + carry) % BASE) as u32;
+        } else {
+            un[j + n] = t as u32;
+        }
+        q[j] = qhat as u32;
+    }
+    un.truncate(n);
+    (trim(q), divmod_small(&trim(un), d).0)
+}
+
+impl BigUint {
+    fn from_vec(v: Vec<u32>) -> Self {
+        Self {
+            digits: Cow::Owned(trim(v)),
+        }
+    }
+
+    /// Returns `true` if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Returns the quotient and the remainder at once.
+    ///
+    /// Complexity: _O(n m)_ for an `n`-limb dividend and an `m`-limb divisor.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = divmod_mag(&self.digits, &rhs.digits);
+        (Self::from_vec(q), Self::from_vec(r))
+    }
+
+    /// Returns the quotient and the remainder by a small divisor at once.
+    ///
+    /// Complexity: _O(n)_.
+    pub fn div_rem_small(&self, rhs: u32) -> (Self, u32) {
+        if rhs as u64 >= BASE {
+            let (q, r) = self.div_rem(&Self::from(rhs));
+            return (
+                q,
+                r.digits.first().map_or(0, |&x| x)
+                    + r.digits.get(1).map_or(0, |&x| x * BASE as u32),
+            );
+        }
+        if rhs == 0 {
+            panic!("attempt to divide by zero");
+        }
+        let (q, r) = divmod_small(&self.digits, rhs);
+        (Self::from_vec(q), r)
+    }
+
+    /// Raises `self` to the power `exp`.
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let mut base = self.clone();
+        let mut res = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = &res * &base;
+            }
+            base = &base * &base;
+            exp >>= 1;
+        }
+        res
+    }
+}
+
+impl BigInt {
+    fn from_parts(neg: bool, mag: BigUint) -> Self {
+        Self {
+            neg: neg && !mag.is_zero(),
+            mag,
+        }
+    }
+
+    /// Returns `true` if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_zero()
+    }
+
+    /// Returns `true` if the number is negative.
+    pub fn is_negative(&self) -> bool {
+        self.neg
+    }
+
+    /// The absolute value.
+    pub fn abs(&self) -> Self {
+        Self::from_parts(false, self.mag.clone())
+    }
+
+    /// The magnitude as a [`BigUint`].
+    pub fn magnitude(&self) -> &BigUint {
+        &self.mag
+    }
+
+    /// Returns the quotient and the remainder at once, truncating toward zero.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = self.mag.div_rem(&rhs.mag);
+        (
+            Self::from_parts(self.neg != rhs.neg, q),
+            Self::from_parts(self.neg, r),
+        )
+    }
+
+    /// Raises `self` to the power `exp`.
+    pub fn pow(&self, exp: u32) -> Self {
+        Self::from_parts(self.neg && exp & 1 == 1, self.mag.pow(exp))
+    }
+}
+
+impl Zero for BigUint {
+    const ZERO: Self = Self {
+        digits: Cow::Borrowed(&[]),
+    };
+}
+
+impl One for BigUint {
+    const ONE: Self = Self {
+        digits: Cow::Borrowed(&[1]),
+    };
+}
+
+impl Zero for BigInt {
+    const ZERO: Self = Self {
+        neg: false,
+        mag: BigUint::ZERO,
+    };
+}
+
+impl One for BigInt {
+    const ONE: Self = Self {
+        neg: false,
+        mag: BigUint::ONE,
+    };
+}
+
+impl LowerBounded for BigUint {
+    const MIN: Self = Self::ZERO;
+}
+
+impl MaybeBounded for BigUint {
+    fn min_value() -> Option<Self> {
+        Some(Self::MIN)
+    }
+
+    fn max_value() -> Option<Self> {
+        None
+    }
+}
+
+impl MaybeBounded for BigInt {
+    fn min_value() -> Option<Self> {
+        None
+    }
+
+    fn max_value() -> Option<Self> {
+        None
+    }
+}
+
+impl Default for BigUint {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Default for BigInt {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+macro_rules! big_from_unsigned_impl {
+    ($($t:ty)*) => ($(
+        impl From<$t> for BigUint {
+            fn from(val: $t) -> Self {
+                let mut val = val as u128;
+                let mut v = vec![];
+                while val > 0 {
+                    v.push((val % BASE as u128) as u32);
+                    val /= BASE as u128;
+                }
+                Self::from_vec(v)
+            }
+        }
+
+        impl From<$t> for BigInt {
+            fn from(val: $t) -> Self {
+                Self::from_parts(false, BigUint::from(val))
+            }
+        }
+    )*)
+}
+
+big_from_unsigned_impl!(usize u8 u16 u32 u64 u128);
+
+macro_rules! big_from_signed_impl {
+    ($($t:ty)*) => ($(
+        impl From<$t> for BigInt {
+            fn from(val: $t) -> Self {
+                Self::from_parts(val < 0, BigUint::from((val as i128).unsigned_abs()))
+            }
+        }
+    )*)
+}
+
+big_from_signed_impl!(isize i8 i16 i32 i64 i128);
+
+impl From<BigUint> for BigInt {
+    fn from(mag: BigUint) -> Self {
+        Self::from_parts(false, mag)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_mag(&self.digits, &other.digits)
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, false) => self.mag.cmp(&other.mag),
+            (true, true) => other.mag.cmp(&self.mag),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Add<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        BigUint::from_vec(add_mag(&self.digits, &rhs.digits))
+    }
+}
+
+impl<'a> Sub<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if self < rhs {
+            panic!("attempt to subtract with overflow");
+        }
+        BigUint::from_vec(sub_mag(&self.digits, &rhs.digits))
+    }
+}
+
+impl<'a> Mul<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        BigUint::from_vec(mul_karatsuba(&self.digits, &rhs.digits))
+    }
+}
+
+impl<'a> Div<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs).0
+    }
+}
+
+impl<'a> Rem<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs).1
+    }
+}
+
+impl<'a> Add<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.neg == rhs.neg {
+            BigInt::from_parts(self.neg, &self.mag + &rhs.mag)
+        } else if self.mag >= rhs.mag {
+            BigInt::from_parts(self.neg, &self.mag - &rhs.mag)
+        } else {
+            BigInt::from_parts(rhs.neg, &rhs.mag - &self.mag)
+        }
+    }
+}
+
+impl<'a> Sub<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + &-rhs
+    }
+}
+
+impl<'a> Mul<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        BigInt::from_parts(self.neg != rhs.neg, &self.mag * &rhs.mag)
+    }
+}
+
+impl<'a> Div<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs).0
+    }
+}
+
+impl<'a> Rem<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs).1
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        BigInt::from_parts(!self.neg, self.mag.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        BigInt::from_parts(!self.neg, self.mag)
+    }
+}
+
+macro_rules! owned_ops_trait_impl {
+    ($($name:ident $fun:tt $ass_name:ident $ass_fun:tt),* for $t:ty) => ($(
+        impl $name for $t {
+            type Output = $t;
+
+            fn $fun(self, rhs: Self) -> Self::Output {
+                (&self).$fun(&rhs)
+            }
+        }
+
+        impl $ass_name for $t {
+            fn $ass_fun(&mut self, rhs: Self) {
+                *self = (&*self).$fun(&rhs);
+            }
+        }
+    )*)
+}
+
+owned_ops_trait_impl!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign,
+    Rem rem RemAssign rem_assign
+    for BigUint
+);
+
+owned_ops_trait_impl!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign,
+    Rem rem RemAssign rem_assign
+    for BigInt
+);
+
+macro_rules! iter_trait_impl {
+    ($($t:ty)*) => ($(
+        impl Sum for $t {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |acc, x| &acc + &x)
+            }
+        }
+
+        impl Product for $t {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ONE, |acc, x| &acc * &x)
+            }
+        }
+    )*)
+}
+
+iter_trait_impl!(BigUint BigInt);
+
+impl FromStr for BigUint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('+').unwrap_or(s).as_bytes();
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return Err(format!("invalid big integer: {:?}", s));
+        }
+        let v = digits
+            .rchunks(WIDTH)
+            .map(|chunk| chunk.iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as u32))
+            .collect();
+        Ok(Self::from_vec(v))
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('-') {
+            Some(rest) if !rest.starts_with('+') => Ok(Self::from_parts(true, rest.parse()?)),
+            _ => Ok(Self::from_parts(false, s.parse()?)),
+        }
+    }
+}
+
+impl Readable for BigUint {
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+        scanner.parse()
+    }
+}
+
+impl Readable for BigInt {
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+        scanner.parse()
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = match self.digits.last() {
+            Some(top) => top.to_string(),
+            None => "0".to_string(),
+        };
+        for d in self.digits.iter().rev().skip(1) {
+            s.push_str(&format!("{:09}", d));
+        }
+        f.pad_integral(true, "", &s)
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(!self.neg, "", &self.mag.to_string())
+    }
+}
+
+impl Debug for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Debug for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::BinaryIndexedTree;
+    use crate::fscanln;
+    use crate::general::binary_search;
+    use crate::num::bigint::{mul_karatsuba, mul_schoolbook};
+    use crate::num::{BigInt, BigUint};
+    use crate::utils::xorshift::Xorshift;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p1601_p1303_p2005() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+1001
+9999999999999999999999999
+2019122920191229201912292019122920191229
+998244353998244353
+"#,
+        ));
+        let (a, b, c, d): (BigUint, BigUint, BigUint, BigUint);
+        fscanln!(reader, a, b, c, d);
+        assert_eq!((&a + &b).to_string(), "10000000000000000000001000");
+        assert_eq!((&a * &b).to_string(), "10009999999999999999999998999");
+        assert_eq!((&c / &d).to_string(), "2022674019746852786594");
+        assert_eq!(&(&(&c / &d) * &d) + &(&c % &d), c);
+    }
+
+    #[test]
+    fn against_i128() {
+        let mut rng = Xorshift::new(0x2545f4914f6cdd1d);
+        let mut rand = || {
+            let x = rng.next_u64();
+            (x as i64 as i128) >> (x % 60)
+        };
+        for _ in 0..1000 {
+            let (x, y) = (rand(), rand());
+            let (a, b) = (BigInt::from(x), BigInt::from(y));
+            assert_eq!(&a + &b, BigInt::from(x + y));
+            assert_eq!(&a - &b, BigInt::from(x - y));
+            assert_eq!(&a * &b, BigInt::from(x * y));
+            assert_eq!(a.cmp(&b), x.cmp(&y));
+            if y != 0 {
+                assert_eq!(&a / &b, BigInt::from(x / y));
+                assert_eq!(&a % &b, BigInt::from(x % y));
+            }
+            assert_eq!(a.to_string(), x.to_string());
+            assert_eq!(x.to_string().parse::<BigInt>(), Ok(a));
+        }
+    }
+
+    #[test]
+    fn karatsuba_and_long_division() {
+        let a: BigUint = "1234567890".repeat(80).parse().unwrap();
+        let b: BigUint = "9876543210123".repeat(40).parse().unwrap();
+        assert_eq!(
+            mul_karatsuba(&a.digits, &b.digits),
+            mul_schoolbook(&a.digits, &b.digits)
+        );
+        let c = &(&a * &b) + &BigUint::from(12345_u32);
+        let (q, r) = c.div_rem(&b);
+        assert_eq!(q, a);
+        assert_eq!(r, BigUint::from(12345_u32));
+        assert_eq!(
+            BigUint::from(10_u32).pow(100).to_string(),
+            format!("1{}", "0".repeat(100))
+        );
+    }
+
+    #[test]
+    fn generic_structures() {
+        // Integer square root of 10^60.
+        let n = BigUint::from(10_u32).pow(60);
+        let r = binary_search(BigUint::from(0_u32)..=n.clone(), |x| &x * &x > n);
+        assert_eq!(r, &BigUint::from(10_u32).pow(30) + &BigUint::from(1_u32));
+
+        let v: Vec<BigInt> = std::iter::once(BigInt::from(0))
+            .chain((1..=5).map(|i| BigInt::from(10).pow(20 * i)))
+            .collect();
+        let mut bit = BinaryIndexedTree::from(v);
+        bit.add(3, -BigInt::from(10).pow(60));
+        assert_eq!(
+            bit.sum(2..=4).to_string(),
+            format!("1{}1{}", "0".repeat(39), "0".repeat(40))
+        );
+    }
+}
@@ -14,6 +14,30 @@ pub trait Bounded: LowerBounded + UpperBounded {}
 
 impl<T> Bounded for T where T: LowerBounded + UpperBounded {}
 
+/// Numbers which may lack a lower or an upper bound, like [`BigInt`](crate::num::BigInt).
+///
+/// This is automatically implemented for [`Bounded`] types.
+pub trait MaybeBounded: Sized {
+    /// Returns the smallest number, if any
+    fn min_value() -> Option<Self>;
+
+    /// Returns the largest number, if any
+    fn max_value() -> Option<Self>;
+}
+
+impl<T> MaybeBounded for T
+where
+    T: Bounded,
+{
+    fn min_value() -> Option<Self> {
+        Some(T::MIN)
+    }
+
+    fn max_value() -> Option<Self> {
+        Some(T::MAX)
+    }
+}
+
 macro_rules! bounded_trait_impl {
     ($t:ty, $min:expr, $max:expr) => {
         impl LowerBounded for $t {
@@ -0,0 +1,299 @@
+use crate::io::{Readable, Scanner};
+use crate::num::modint::{inv_mod, mul_mod};
+use crate::num::{One, Zero};
+use std::cell::Cell;
+use std::fmt::{Debug, Display};
+use std::io::BufRead;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+thread_local! {
+    // The modulus and ceil(2^64 / modulus), for Barrett reduction.
+    static MODULUS: Cell<(u64, u64)> = const { Cell::new((998244353, u64::MAX / 998244353 + 1)) };
+}
+
+/// Integer modulo a runtime modulus, always kept in `0..modulus`.
+///
+/// The modulus is shared by all values of the current thread,
+/// and defaults to `998244353` until [`DynModInt::set_modulus`] is called.
+/// Values created before changing the modulus are not reduced again.
+///
+/// Unlike [`ModInt`](crate::num::ModInt), whose [`One::ONE`] is `1 % M`, [`One::ONE`] is the constant `1`
+/// here, so the modulus must be greater than `1`.
+///
+/// # Examples
+/// ```
+/// use cplit::num::DynModInt;
+///
+/// DynModInt::set_modulus(13);
+/// let a = DynModInt::from(5);
+/// assert_eq!(a * a.inv(), DynModInt::from(1));
+/// assert_eq!(a.pow(2).val(), 12);
+/// assert_eq!(-a, DynModInt::from(8));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DynModInt(u64);
+
+impl DynModInt {
+    /// Sets the modulus of the current thread.
+    ///
+    /// Panics if `m` is not greater than `1`.
+    pub fn set_modulus(m: u64) {
+        assert!(m > 1, "The modulus must be greater than 1");
+        MODULUS.with(|c| c.set((m, u64::MAX / m + 1)));
+    }
+
+    /// The modulus of the current thread.
+    pub fn modulus() -> u64 {
+        MODULUS.with(|c| c.get().0)
+    }
+
+    /// Constructs a new modular integer from `val`, which is reduced modulo the modulus.
+    pub fn new(val: u64) -> Self {
+        Self(val % Self::modulus())
+    }
+
+    /// Constructs a new modular integer from `val` already in `0..modulus`.
+    pub const fn raw(val: u64) -> Self {
+        Self(val)
+    }
+
+    /// The value in `0..modulus`.
+    pub fn val(self) -> u64 {
+        self.0
+    }
+
+    /// Raises `self` to the power `exp`.
+    ///
+    /// Complexity: _O(log exp)_.
+    pub fn pow(self, mut exp: u64) -> Self {
+        let (m, im) = MODULUS.with(|c| c.get());
+        let mut base = self.0;
+        let mut res = 1 % m;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = mul_mod(res, base, m, im);
+            }
+            base = mul_mod(base, base, m, im);
+            exp >>= 1;
+        }
+        Self(res)
+    }
+
+    /// The multiplicative inverse, by the extended Euclidean algorithm.
+    ///
+    /// Panics if `self` is not coprime with the modulus.
+    pub fn inv(self) -> Self {
+        Self(inv_mod(self.0, Self::modulus()))
+    }
+}
+
+impl Zero for DynModInt {
+    const ZERO: Self = Self(0);
+}
+
+impl One for DynModInt {
+    const ONE: Self = Self(1);
+}
+
+macro_rules! dyn_modint_from_unsigned_impl {
+    ($($t:ty)*) => ($(
+        impl From<$t> for DynModInt {
+            fn from(val: $t) -> Self {
+                Self((val as u128 % Self::modulus() as u128) as u64)
+            }
+        }
+    )*)
+}
+
+dyn_modint_from_unsigned_impl!(usize u8 u16 u32 u64 u128);
+
+macro_rules! dyn_modint_from_signed_impl {
+    ($($t:ty)*) => ($(
+        impl From<$t> for DynModInt {
+            fn from(val: $t) -> Self {
+                Self((val as i128).rem_euclid(Self::modulus() as i128) as u64)
+            }
+        }
+    )*)
+}
+
+dyn_modint_from_signed_impl!(isize i8 i16 i32 i64 i128);
+
+impl Add for DynModInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let m = Self::modulus();
+        let (v, overflow) = self.0.overflowing_add(rhs.0);
+        if overflow || v >= m {
+            Self(v.wrapping_sub(m))
+        } else {
+            Self(v)
+        }
+    }
+}
+
+impl Sub for DynModInt {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (v, borrow) = self.0.overflowing_sub(rhs.0);
+        if borrow {
+            Self(v.wrapping_add(Self::modulus()))
+        } else {
+            Self(v)
+        }
+    }
+}
+
+impl Mul for DynModInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (m, im) = MODULUS.with(|c| c.get());
+        Self(mul_mod(self.0, rhs.0, m, im))
+    }
+}
+
+impl Div for DynModInt {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+impl Neg for DynModInt {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::ZERO - self
+    }
+}
+
+macro_rules! ass_ops_trait_impl {
+    ($($name:ident $fun:tt $op:tt),*) => ($(
+        impl $name for DynModInt {
+            fn $fun(&mut self, rhs: Self) {
+                *self = *self $op rhs;
+            }
+        }
+    )*)
+}
+
+ass_ops_trait_impl!(AddAssign add_assign +, SubAssign sub_assign -, MulAssign mul_assign *, DivAssign div_assign /);
+
+impl Sum for DynModInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Product for DynModInt {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+impl FromStr for DynModInt {
+    type Err = String;
+
+    /// Parses a decimal integer of any length, reducing it modulo the modulus.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (neg, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() {
+            return Err(format!("invalid modular integer: {:?}", s));
+        }
+        let ten = Self::from(10_u64);
+        let mut res = Self::ZERO;
+        for c in digits.bytes() {
+            if !c.is_ascii_digit() {
+                return Err(format!("invalid modular integer: {:?}", s));
+            }
+            res = res * ten + Self::from(c - b'0');
+        }
+        Ok(if neg { -res } else { res })
+    }
+}
+
+impl Readable for DynModInt {
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+        scanner.parse()
+    }
+}
+
+impl Display for DynModInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Debug for DynModInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::segment_tree::AddSum;
+    use crate::data_structure::{BinaryIndexedTree, SegmentTree};
+    use crate::fscanln;
+    use crate::num::DynModInt;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn luogu_p3811() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+10 13
+"#,
+        ));
+        let (n, p): (usize, u64);
+        fscanln!(reader, n, p);
+        DynModInt::set_modulus(p);
+        let mut inv = vec![DynModInt::from(1); n + 1];
+        for i in 2..=n {
+            inv[i] = -DynModInt::from(p / i as u64) * inv[p as usize % i];
+        }
+        assert_eq!(
+            inv[1..].iter().map(|x| x.val()).collect::<Vec<_>>(),
+            vec![1, 7, 9, 10, 8, 11, 2, 5, 3, 4]
+        );
+        assert!((1..=n).all(|i| inv[i] == DynModInt::from(i).inv()));
+    }
+
+    #[test]
+    fn data_structures() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+4 1000000007
+-1 2 3 1000000010
+"#,
+        ));
+        let (n, p): (usize, u64);
+        fscanln!(reader, n, p);
+        DynModInt::set_modulus(p);
+        let v: Vec<DynModInt>;
+        fscanln!(reader, v; n);
+        let mut bit = BinaryIndexedTree::from(v.clone());
+        bit.add(1, DynModInt::from(5));
+        assert_eq!(bit.sum(..).val(), 12);
+
+        let mut st = SegmentTree::<DynModInt, DynModInt, AddSum>::from(v);
+        st.update(2..=3, DynModInt::from(p - 2));
+        assert_eq!(st.query(1..=3).val(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "The modulus must be greater than 1")]
+    fn modulus_one() {
+        DynModInt::set_modulus(1);
+    }
+}
@@ -4,9 +4,21 @@
 use std::cmp::{Eq, Ord, PartialEq, PartialOrd};
 use std::ops::{Add, Div, Mul, Rem, Sub};
 use std::ops::{AddAssign, DivAssign, MulAssign, RemAssign, SubAssign};
+pub mod bigint;
 pub mod bounds;
+pub mod dyn_modint;
+pub mod modint;
+pub mod rational;
 #[doc(inline)]
-pub use self::bounds::{Bounded, LowerBounded, UpperBounded};
+pub use self::bigint::{BigInt, BigUint};
+#[doc(inline)]
+pub use self::bounds::{Bounded, LowerBounded, MaybeBounded, UpperBounded};
+#[doc(inline)]
+pub use self::dyn_modint::DynModInt;
+#[doc(inline)]
+pub use self::modint::{ModInt, ModInt1000000007, ModInt998244353};
+#[doc(inline)]
+pub use self::rational::Rational;
 
 /// The base trait for numeric types
 pub trait Numeric: Default + Zero + One {}
@@ -104,6 +116,8 @@ macro_rules! tuple_zero_impl {
 }
 
 tuple_zero_impl!(A B);
+tuple_zero_impl!(A B C);
+tuple_zero_impl!(A B C D);
 
 macro_rules! tuple_one_impl {
     ( $( $name:ident )+ ) => {
@@ -115,3 +129,5 @@ macro_rules! tuple_one_impl {
 }
 
 tuple_one_impl!(A B);
+tuple_one_impl!(A B C);
+tuple_one_impl!(A B C D);
@@ -0,0 +1,338 @@
+use crate::io::{Readable, Scanner};
+use crate::num::{One, Zero};
+use std::fmt::{Debug, Display};
+use std::io::BufRead;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Integer modulo a constant `M`, always kept in `0..M`.
+///
+/// Multiplication uses Barrett reduction when `M < 2^32`, and falls back to `u128` otherwise.
+/// Division multiplies by the inverse, which panics if it does not exist.
+///
+/// For more information, see [Modular arithmetic](https://cp-algorithms.com/algebra/module-inverse.html).
+///
+/// # Examples
+/// ```
+/// use cplit::num::ModInt998244353 as Mint;
+///
+/// let a = Mint::from(3);
+/// assert_eq!(a.pow(998244352), Mint::from(1));
+/// assert_eq!(a * a.inv(), Mint::from(1));
+/// assert_eq!((Mint::from(1) / a).to_string(), "332748118");
+/// assert_eq!("-1".parse::<Mint>().unwrap().val(), 998244352);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64>(u64);
+
+pub type ModInt998244353 = ModInt<998244353>;
+pub type ModInt1000000007 = ModInt<1000000007>;
+
+impl<const M: u64> ModInt<M> {
+    // ceil(2^64 / M), for Barrett reduction.
+    const IM: u64 = (u64::MAX / M).wrapping_add(1);
+
+    /// Constructs a new modular integer from `val`, which is reduced modulo `M`.
+    pub fn new(val: u64) -> Self {
+        Self(val % M)
+    }
+
+    /// Constructs a new modular integer from `val` already in `0..M`.
+    pub const fn raw(val: u64) -> Self {
+        Self(val)
+    }
+
+    /// The modulus.
+    pub const fn modulus() -> u64 {
+        M
+    }
+
+    /// The value in `0..M`.
+    pub fn val(self) -> u64 {
+        self.0
+    }
+
+    /// Raises `self` to the power `exp`.
+    ///
+    /// Complexity: _O(log exp)_.
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut res = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// The multiplicative inverse, by the extended Euclidean algorithm.
+    ///
+    /// Panics if `self` is not coprime with `M`.
+    pub fn inv(self) -> Self {
+        Self(inv_mod(self.0, M))
+    }
+}
+
+/// Computes `a * b % m` for `a, b < m`, with `im = ceil(2^64 / m)`.
+///
+/// Uses Barrett reduction when `m < 2^32`, and `u128` otherwise.
+pub(crate) fn mul_mod(a: u64, b: u64, m: u64, im: u64) -> u64 {
+    if m < 1 << 32 {
+        let z = a * b;
+        let x = ((z as u128 * im as u128) >> 64) as u64;
+        let (v, borrow) = z.overflowing_sub(x.wrapping_mul(m));
+        if borrow {
+            v.wrapping_add(m)
+        } else {
+            v
+        }
+    } else {
+        (a as u128 * b as u128 % m as u128) as u64
+    }
+}
+
+/// Computes the inverse of `a` modulo `m` by the extended Euclidean algorithm.
+///
+/// Panics if `a` is not coprime with `m`.
+pub(crate) fn inv_mod(a: u64, m: u64) -> u64 {
+    let (mut g, mut b) = (a as i128, m as i128);
+    let (mut x, mut y) = (1_i128, 0_i128);
+    while b != 0 {
+        let q = g / b;
+        (g, b) = (b, g - q * b);
+        (x, y) = (y, x - q * y);
+    }
+    if g != 1 {
+        panic!("{} has no inverse modulo {}", a, m);
+    }
+    x.rem_euclid(m as i128) as u64
+}
+
+impl<const M: u64> Zero for ModInt<M> {
+    const ZERO: Self = Self(0);
+}
+
+impl<const M: u64> One for ModInt<M> {
+    const ONE: Self = Self(1 % M);
+}
+
+macro_rules! modint_from_unsigned_impl {
+    ($($t:ty)*) => ($(
+        impl<const M: u64> From<$t> for ModInt<M> {
+            fn from(val: $t) -> Self {
+                Self((val as u128 % M as u128) as u64)
+            }
+        }
+    )*)
+}
+
+modint_from_unsigned_impl!(usize u8 u16 u32 u64 u128);
+
+macro_rules! modint_from_signed_impl {
+    ($($t:ty)*) => ($(
+        impl<const M: u64> From<$t> for ModInt<M> {
+            fn from(val: $t) -> Self {
+                Self((val as i128).rem_euclid(M as i128) as u64)
+            }
+        }
+    )*)
+}
+
+modint_from_signed_impl!(isize i8 i16 i32 i64 i128);
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (v, overflow) = self.0.overflowing_add(rhs.0);
+        if overflow || v >= M {
+            Self(v.wrapping_sub(M))
+        } else {
+            Self(v)
+        }
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (v, borrow) = self.0.overflowing_sub(rhs.0);
+        if borrow {
+            Self(v.wrapping_add(M))
+        } else {
+            Self(v)
+        }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(mul_mod(self.0, rhs.0, M, Self::IM))
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv()
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::ZERO - self
+    }
+}
+
+macro_rules! ass_ops_trait_impl {
+    ($($name:ident $fun:tt $op:tt),*) => ($(
+        impl<const M: u64> $name for ModInt<M> {
+            fn $fun(&mut self, rhs: Self) {
+                *self = *self $op rhs;
+            }
+        }
+    )*)
+}
+
+ass_ops_trait_impl!(AddAssign add_assign +, SubAssign sub_assign -, MulAssign mul_assign *, DivAssign div_assign /);
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+impl<const M: u64> FromStr for ModInt<M> {
+    type Err = String;
+
+    /// Parses a decimal integer of any length, reducing it modulo `M`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (neg, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() {
+            return Err(format!("invalid modular integer: {:?}", s));
+        }
+        let ten = Self::from(10_u64);
+        let mut res = Self::ZERO;
+        for c in digits.bytes() {
+            if !c.is_ascii_digit() {
+                return Err(format!("invalid modular integer: {:?}", s));
+            }
+            res = res * ten + Self::from(c - b'0');
+        }
+        Ok(if neg { -res } else { res })
+    }
+}
+
+impl<const M: u64> Readable for ModInt<M> {
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+        scanner.parse()
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<const M: u64> Debug for ModInt<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_structure::segment_tree::AddSum;
+    use crate::data_structure::{BinaryIndexedTree, SegmentTree};
+    use crate::num::{ModInt, ModInt1000000007, ModInt998244353};
+    use crate::number_theory::euler_sieve::{euler_sieve, MulFunc};
+
+    #[test]
+    fn arithmetic() {
+        type Mint = ModInt998244353;
+        let a = Mint::from(-5_i64);
+        assert_eq!(a.val(), 998244348);
+        assert_eq!(a + Mint::from(7), Mint::from(2));
+        assert_eq!(
+            Mint::from(2).pow(23) * Mint::from(119) + Mint::from(1),
+            Mint::from(0)
+        );
+        assert_eq!(Mint::from(12345) / Mint::from(12345), Mint::from(1));
+        assert_eq!(
+            "123456789012345678901234567890".parse::<ModInt1000000007>(),
+            Ok(ModInt1000000007::from(123456789012345678901234567890_u128))
+        );
+        assert_eq!(
+            (1..=10).map(Mint::from).product::<Mint>(),
+            Mint::from(3628800)
+        );
+
+        // Large modulus without Barrett reduction.
+        type Big = ModInt<{ (1 << 61) - 1 }>;
+        let b = Big::from(u64::MAX);
+        assert_eq!(b * b.inv(), Big::from(1));
+        assert_eq!(Big::from(3).pow((1 << 61) - 2), Big::from(1));
+    }
+
+    #[test]
+    fn data_structures() {
+        type Mint = ModInt1000000007;
+        let v: Vec<Mint> = [0, 1_000_000_006, 2, 3, 4]
+            .into_iter()
+            .map(Mint::from)
+            .collect();
+        let mut bit = BinaryIndexedTree::from(v.clone());
+        bit.add(2, Mint::from(1_000_000_000));
+        assert_eq!(bit.sum(1..=3), Mint::from(1_000_000_004));
+
+        let mut st = SegmentTree::<Mint, Mint, AddSum>::from(v);
+        st.update(.., Mint::from(-1));
+        assert_eq!(st.query(..), Mint::from(4));
+    }
+
+    #[test]
+    fn sieve() {
+        type Mint = ModInt998244353;
+        /// Number of divisors, along with the exponent of the smallest prime factor.
+        struct DivisorCount;
+
+        impl MulFunc<(Mint, u32)> for DivisorCount {
+            const ONE: (Mint, u32) = (Mint::raw(1), 0);
+            const P: fn(usize, usize) -> (Mint, u32) = |_, _| (Mint::from(2), 1);
+            const DERIVE_DIVIDES: fn(usize, usize, &dyn Fn(usize) -> (Mint, u32)) -> (Mint, u32) =
+                |_, x, f| {
+                    let (d, e) = f(x);
+                    (d / Mint::from(e + 1) * Mint::from(e + 2), e + 1)
+                };
+            const DERIVE_COPRIME: fn(usize, usize, &dyn Fn(usize) -> (Mint, u32)) -> (Mint, u32) =
+                |p, x, f| (f(x).0 * f(p).0, 1);
+        }
+
+        let (_, d) = euler_sieve::<_, DivisorCount>(100);
+        assert_eq!(d[1].0, Mint::from(1));
+        assert_eq!(d[12].0, Mint::from(6));
+        assert_eq!(d[64].0, Mint::from(7));
+        assert_eq!(d[60].0, Mint::from(12));
+    }
+}
@@ -0,0 +1,336 @@
+use crate::io::{Readable, Scanner};
+use crate::num::{IntegerOps, Numeric, NumericCmpOps, One, Zero};
+use crate::number_theory::gcd;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+use std::io::BufRead;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Exact fraction `num / den` over any integer type, including [`BigInt`](crate::num::BigInt).
+///
+/// Always kept normalized: `den > 0` and `gcd(num, den) == 1`,
+/// so the derived equality and hashing are exact.
+/// Dividing by zero panics.
+///
+/// # Examples
+/// ```
+/// use cplit::num::Rational;
+///
+/// let a = Rational::new(6_i64, -4);
+/// assert_eq!((a.numer(), a.denom()), (&-3, &2));
+/// assert_eq!(a + Rational::from(2), Rational::new(1, 2));
+/// assert_eq!((a * a).to_string(), "9/4");
+/// assert!(a < Rational::new(-4, 3));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    num: T,
+    den: T,
+}
+
+impl<T> Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    /// Constructs a new fraction `num / den` and normalizes it.
+    ///
+    /// Panics if `den` is zero.
+    pub fn new(num: T, den: T) -> Self {
+        if den == T::ZERO {
+            panic!("attempt to divide by zero");
+        }
+        let g = gcd(num.clone(), den.clone());
+        let (num, den) = (num / g.clone(), den / g);
+        if den < T::ZERO {
+            Self {
+                num: T::ZERO - num,
+                den: T::ZERO - den,
+            }
+        } else {
+            Self { num, den }
+        }
+    }
+
+    /// The numerator, which carries the sign.
+    pub fn numer(&self) -> &T {
+        &self.num
+    }
+
+    /// The denominator, always positive.
+    pub fn denom(&self) -> &T {
+        &self.den
+    }
+
+    /// Returns `true` if the denominator is `1`.
+    pub fn is_integer(&self) -> bool {
+        self.den == T::ONE
+    }
+
+    /// The reciprocal `den / num`.
+    ///
+    /// Panics if `self` is zero.
+    pub fn recip(&self) -> Self {
+        Self::new(self.den.clone(), self.num.clone())
+    }
+
+    /// The largest integer not greater than `self`.
+    pub fn floor(&self) -> T {
+        let q = self.num.clone() / self.den.clone();
+        if self.num < T::ZERO && q.clone() * self.den.clone() != self.num {
+            q - T::ONE
+        } else {
+            q
+        }
+    }
+
+    /// The smallest integer not less than `self`.
+    pub fn ceil(&self) -> T {
+        let q = self.num.clone() / self.den.clone();
+        if self.num > T::ZERO && q.clone() * self.den.clone() != self.num {
+            q + T::ONE
+        } else {
+            q
+        }
+    }
+}
+
+impl<T: Zero + One> Zero for Rational<T> {
+    const ZERO: Self = Self {
+        num: T::ZERO,
+        den: T::ONE,
+    };
+}
+
+impl<T: One> One for Rational<T> {
+    const ONE: Self = Self {
+        num: T::ONE,
+        den: T::ONE,
+    };
+}
+
+impl<T: Zero + One> Default for Rational<T> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<T: One> From<T> for Rational<T> {
+    fn from(num: T) -> Self {
+        Self { num, den: T::ONE }
+    }
+}
+
+impl<T> Ord for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num.clone() * other.den.clone()).cmp(&(other.num.clone() * self.den.clone()))
+    }
+}
+
+impl<T> PartialOrd for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Add for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let g = gcd(self.den.clone(), rhs.den.clone());
+        let (l, r) = (self.den.clone() / g.clone(), rhs.den / g);
+        Self::new(self.num * r.clone() + rhs.num * l, self.den * r)
+    }
+}
+
+impl<T> Sub for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T> Mul for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // Cross-reduce first, so the result is already normalized.
+        let g1 = gcd(self.num.clone(), rhs.den.clone());
+        let g2 = gcd(rhs.num.clone(), self.den.clone());
+        Self {
+            num: self.num / g1.clone() * (rhs.num / g2.clone()),
+            den: self.den / g2 * (rhs.den / g1),
+        }
+    }
+}
+
+impl<T> Div for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.recip()
+    }
+}
+
+impl<T> Neg for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            num: T::ZERO - self.num,
+            den: self.den,
+        }
+    }
+}
+
+macro_rules! ass_ops_trait_impl {
+    ($($name:ident $fun:tt $op:tt),*) => ($(
+        impl<T> $name for Rational<T>
+        where
+            T: Numeric + IntegerOps + NumericCmpOps + Clone,
+        {
+            fn $fun(&mut self, rhs: Self) {
+                *self = self.clone() $op rhs;
+            }
+        }
+    )*)
+}
+
+ass_ops_trait_impl!(AddAssign add_assign +, SubAssign sub_assign -, MulAssign mul_assign *, DivAssign div_assign /);
+
+impl<T> Sum for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<T> Product for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+impl<T> FromStr for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone + FromStr,
+{
+    type Err = String;
+
+    /// Parses `num/den` or an integer `num`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |_| format!("invalid rational number: {:?}", s);
+        match s.split_once('/') {
+            Some((num, den)) => {
+                let (num, den) = (num.parse().map_err(err)?, den.parse().map_err(err)?);
+                if den == T::ZERO {
+                    return Err(format!("invalid rational number: {:?}", s));
+                }
+                Ok(Self::new(num, den))
+            }
+            None => Ok(Self::from(s.parse::<T>().map_err(err)?)),
+        }
+    }
+}
+
+impl<T> Readable for Rational<T>
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone + FromStr,
+{
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self {
+        scanner.parse()
+    }
+}
+
+impl<T: Display + One + PartialEq> Display for Rational<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == T::ONE {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl<T: Display + One + PartialEq> Debug for Rational<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fscanln;
+    use crate::geometry::{Line, Point};
+    use crate::num::{BigInt, Rational};
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn arithmetic() {
+        type Q = Rational<i64>;
+        let h: Q = (1..=10).map(|i| Q::new(1, i)).sum();
+        assert_eq!(h, Q::new(7381, 2520));
+        assert_eq!(h.floor(), 2);
+        assert_eq!(h.ceil(), 3);
+        assert_eq!((-h).floor(), -3);
+        assert_eq!(Q::new(3, 4) - Q::new(3, 4), Q::new(0, 5));
+        assert_eq!(Q::new(2, 3) / Q::new(-4, 9), Q::new(-3, 2));
+        assert!(Q::new(-1, 2) < Q::new(-1, 3));
+        assert_eq!("-6/8".parse::<Q>(), Ok(Q::new(-3, 4)));
+        assert!("1/0".parse::<Q>().is_err());
+
+        // Exact even when the primitive types would overflow.
+        type BigQ = Rational<BigInt>;
+        let x = BigQ::new(BigInt::from(10).pow(30), BigInt::from(3).pow(40));
+        assert_eq!((x.clone() * x.recip()).to_string(), "1");
+        assert_eq!(x.denom(), &BigInt::from(3).pow(40));
+    }
+
+    #[test]
+    fn exact_geometry() {
+        let mut reader = BufReader::new(Cursor::new(
+            r#"
+1/3 0 1/3 1
+0 1/7 1 2/7
+"#,
+        ));
+        type Q = Rational<i64>;
+        let (x1, y1, x2, y2, x3, y3, x4, y4): (Q, Q, Q, Q, Q, Q, Q, Q);
+        fscanln!(reader, x1, y1, x2, y2, x3, y3, x4, y4);
+        let l1 = Line::new(Point::new(x1, y1), Point::new(x2, y2));
+        let l2 = Line::new(Point::new(x3, y3), Point::new(x4, y4));
+        let p = l1.intersection(&l2).unwrap();
+        assert_eq!(p, Point::new(Q::new(1, 3), Q::new(4, 21)));
+        assert!(l1.on_line(&p) && l2.on_line(&p));
+        assert!(l1.intersection(&l1).is_none());
+    }
+}
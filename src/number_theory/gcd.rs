@@ -0,0 +1,40 @@
+use crate::num::{IntegerOps, Numeric, NumericCmpOps};
+
+/// Computes the non-negative greatest common divisor of `a` and `b` by the Euclidean algorithm.
+///
+/// `gcd(0, 0)` is `0`. Works for signed, unsigned and big integers.
+///
+/// Complexity: _O(log min(a, b))_ divisions.
+pub fn gcd<T>(mut a: T, mut b: T) -> T
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    while b != T::ZERO {
+        let r = a % b.clone();
+        a = b;
+        b = r;
+    }
+    if a < T::ZERO {
+        T::ZERO - a
+    } else {
+        a
+    }
+}
+
+/// Computes the non-negative least common multiple of `a` and `b`.
+///
+/// `lcm(0, x)` is `0`.
+pub fn lcm<T>(a: T, b: T) -> T
+where
+    T: Numeric + IntegerOps + NumericCmpOps + Clone,
+{
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    let l = a.clone() / gcd(a, b.clone()) * b;
+    if l < T::ZERO {
+        T::ZERO - l
+    } else {
+        l
+    }
+}
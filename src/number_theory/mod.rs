@@ -1,4 +1,7 @@
 pub mod euler_sieve;
+pub mod gcd;
 
 #[doc(inline)]
 pub use self::euler_sieve::euler_sieve;
+#[doc(inline)]
+pub use self::gcd::{gcd, lcm};
@@ -2,6 +2,9 @@ use crate::num::{One, Zero};
 use std::cmp::Ordering;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+#[cfg(test)]
+pub(crate) mod xorshift;
+
 pub trait Flag {
     fn set(&mut self, val: bool);
     fn get(&self) -> bool;
@@ -0,0 +1,22 @@
+/// Xorshift pseudo-random generator for randomized tests and benchmarks.
+pub(crate) struct Xorshift(u64);
+
+impl Xorshift {
+    /// Constructs a generator from a nonzero `seed`.
+    pub(crate) fn new(seed: u64) -> Self {
+        Xorshift(seed)
+    }
+
+    /// Returns the next pseudo-random 64-bit word.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a pseudo-random number in `0..n`.
+    pub(crate) fn next(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}